@@ -0,0 +1,69 @@
+//! Counts the examples in each puzzle's `test_cases` so `puzzle_tests!` can give every one of
+//! them its own test.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// The examples listed for each part in `test_cases`, ignoring commented out ones.
+fn count_examples(source: &str) -> (usize, usize) {
+    let start = match source.find("fn test_cases") {
+        Some(start) => start,
+        None => return (0, 0),
+    };
+    let (mut a, mut b) = (0, 0);
+    let mut depth = 0;
+    for line in source[start..].lines() {
+        let code = line.split("//").next().unwrap();
+        a += code.matches("Part::A").count();
+        b += code.matches("Part::B").count();
+        depth += code.matches('{').count() as i32 - code.matches('}').count() as i32;
+        if depth <= 0 && code.contains('}') {
+            break;
+        }
+    }
+    (a, b)
+}
+
+fn indices(count: usize) -> String {
+    (0..count)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("puzzle_tests");
+    let mut files = vec![];
+    rust_files(&root.join("src"), &mut files);
+    for file in files {
+        let source = fs::read_to_string(&file).unwrap();
+        let invocation = source
+            .lines()
+            .find_map(|line| line.strip_prefix("puzzle_tests!(")?.strip_suffix(");"));
+        if let Some(args) = invocation {
+            let (a, b) = count_examples(&source);
+            let target = out.join(file.strip_prefix(&root).unwrap());
+            fs::create_dir_all(target.parent().unwrap()).unwrap();
+            let tests = format!(
+                "puzzle_tests!(@cases {args}, a: [{}], b: [{}]);\n",
+                indices(a),
+                indices(b)
+            );
+            fs::write(target, tests).unwrap();
+        }
+    }
+}
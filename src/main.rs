@@ -1,6 +1,6 @@
 #![allow(unused_must_use, unused_imports, unused_mut, dead_code)]
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 mod year2017;
 mod year2019;
@@ -11,15 +11,115 @@ mod utils;
 
 use utils::*;
 
-use crate::utils::puzzle::PuzzleFns;
+use crate::utils::cli::{Args, Command};
+use crate::utils::puzzle::{submit_manual, AoC, PuzzleFns, Verbosity};
+
+fn puzzles() -> AoC {
+    AoC::new()
+        .register::<year2017::day1::AoC2017Day1>()
+        .register::<year2019::day17::AoC2019Day17>()
+        .register::<year2019::day18::AoC2019Day18>()
+        .register::<year2019::day19::AoC2019Day19>()
+        .register::<year2021::day1::Year2021Day1>()
+        .register::<year2021::day6::AoC2021Day6>()
+        .register::<year2021::day13::AoC2021Day13>()
+        .register::<year2023::day1::AoC2023Day1>()
+        .register::<year2023::day2::AoC2023Day2>()
+        .register::<year2023::day3::AoC2023Day3>()
+        .register::<year2023::day4::AoC2023Day4>()
+        .register::<year2023::day5::AoC2023Day5>()
+        .register::<year2023::day6::AoC2023Day6>()
+        .register::<year2023::day7::AoC2023Day7>()
+        .register::<year2023::day8::AoC2023Day8>()
+}
 
 fn main() {
     let _days = 256;
     //year2021::day6::calc_fish_buckets(days);
     let now = Instant::now();
+    let Args {
+        command,
+        options,
+        verbosity,
+        timeout,
+    } = Args::parse(std::env::args().skip(1)).unwrap();
+    // Batch runs stay quiet so the results aren't lost in solver output.
+    let quiet = matches!(
+        command,
+        Command::Test { .. } | Command::RunAll | Command::Bench { .. }
+    );
+    verbosity
+        .unwrap_or(if quiet {
+            Verbosity::Quiet
+        } else {
+            Verbosity::Info
+        })
+        .set_default();
+    // Don't let one solver that never finishes hold up a whole batch.
+    let timeout = Some(timeout.unwrap_or(Duration::from_secs(60)));
+
+    match command {
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+        } => {
+            let res = submit_manual(year, day, part, &answer, &options).unwrap();
+            eprintln!("{res:?}");
+            return;
+        }
+        Command::Solve {
+            puzzle: (year, day),
+        } => {
+            let puzzle = puzzles().get(year, day).unwrap().clone();
+            puzzle.run_tests().unwrap();
+            let res = puzzle.try_submit_with(&options).unwrap();
+            eprintln!("{res:?}");
+            return;
+        }
+        Command::Test { puzzle } => {
+            let aoc = puzzles();
+            let passed = match puzzle {
+                Some((year, day)) => aoc
+                    .test_puzzle(year, day)
+                    .map_err(|err| eprintln!("{err}"))
+                    .is_ok(),
+                None => aoc.test_all().iter().fold(true, |passed, report| {
+                    print!("{report}");
+                    passed && report.passed()
+                }),
+            };
+            if !passed {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Command::RunAll => {
+            for result in puzzles().run_all(1, timeout, true) {
+                print!("{result}");
+            }
+            return;
+        }
+        Command::Bench { puzzle, iterations } => {
+            let aoc = puzzles();
+            match puzzle {
+                Some((year, day)) => print!(
+                    "{}",
+                    aoc.run_puzzle(year, day, iterations, timeout, false)
+                        .unwrap()
+                ),
+                None => aoc
+                    .run_all(iterations, timeout, false)
+                    .for_each(|result| print!("{result}")),
+            }
+            return;
+        }
+        Command::Default => (),
+    }
 
     year2023::day8::AoC2023Day8::run_tests().unwrap();
-    let res = year2023::day8::AoC2023Day8::try_submit().unwrap();
+    let res = year2023::day8::AoC2023Day8::try_submit_with(&options).unwrap();
     eprintln!("{res:?}");
     let elapsed = now.elapsed();
     println!("{} ms", elapsed.as_millis());
@@ -114,6 +114,10 @@ pub fn read_input(year: PuzzleYear, day: PuzzleDay) -> Option<String> {
 pub fn get_input(session_cookie: &str, year: PuzzleYear, day: PuzzleDay) -> Result<String> {
     if let Some(cached) = read_input(year, day) {
         Ok(cached)
+    } else if session_cookie.is_empty() {
+        Err(anyhow!(
+            "No cached input for {year} day {day} and no session cookie"
+        ))
     } else {
         let input = download_input(session_cookie, year, day)?;
         let path = get_puzzle_dir(year, day).join("input.txt");
@@ -1,18 +1,22 @@
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
+    fmt::Display,
     fs::{self, read_to_string, File},
-    io::Read,
+    io::{Read, Write},
+    str::FromStr,
 };
 
 use super::{
-    aocapi::{get_puzzle_dir, submit_answer},
+    aocapi::{get_puzzle_dir, read_puzzle, submit_answer},
     puzzle::{PuzzleDay, PuzzleYear},
-    puzzleanswer::PuzzleAnswer,
+    puzzleanswer::{Answer, PuzzleAnswer},
 };
-use anyhow::Result;
+use anyhow::{anyhow, Error, Result};
+use chrono::{DateTime, Utc};
 use html2text::from_read;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone)]
 pub enum AoCData {
@@ -20,7 +24,7 @@ pub enum AoCData {
     Raw(String),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Part {
     A,
     B,
@@ -35,6 +39,18 @@ impl Part {
     }
 }
 
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" | "1" => Ok(Part::A),
+            "b" | "B" | "2" => Ok(Part::B),
+            _ => Err(anyhow!("Invalid part {s}, expected a or b.")),
+        }
+    }
+}
+
 impl AoCData {
     pub fn get_value(&self) -> Result<String> {
         match self {
@@ -52,6 +68,8 @@ pub struct TestCase {
     pub part: Part,
     pub input: AoCData,
     pub expected: AoCData,
+    /// Constants that differ between the examples and the real input, see `PuzzleContext::param`.
+    pub params: BTreeMap<String, String>,
 }
 
 impl TestCase {
@@ -60,6 +78,7 @@ impl TestCase {
             part,
             input: AoCData::Raw(input.to_string()),
             expected: AoCData::Raw(expected.to_string()),
+            params: BTreeMap::new(),
         }
     }
     pub fn from_files(part: Part, input: impl ToString, expected: impl ToString) -> Self {
@@ -67,8 +86,14 @@ impl TestCase {
             part,
             input: AoCData::File(input.to_string()),
             expected: AoCData::File(expected.to_string()),
+            params: BTreeMap::new(),
         }
     }
+
+    pub fn with_param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.insert(key.to_owned(), value.to_string());
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -97,56 +122,202 @@ impl BadAnswers {
             BadAnswers::Raw(_) => false,
         }
     }
+}
 
-    pub fn from_response(response: &str, answer: &str) -> Self {
-        let too_high = Regex::new(r"answer is too high").unwrap();
-        let too_low = Regex::new(r"answer is too low").unwrap();
-        let is_num = answer.parse::<i128>();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    AlreadyComplete,
+}
 
-        if let Ok(num) = is_num {
-            if too_high.is_match(response) {
-                BadAnswers::LessThan(num)
-            } else if too_low.is_match(response) {
-                BadAnswers::GreaterThan(num)
+impl Outcome {
+    pub fn from_response(response: &str) -> Self {
+        if Regex::new(r"already complete").unwrap().is_match(response) {
+            Outcome::AlreadyComplete
+        } else if Regex::new(r"not the right answer")
+            .unwrap()
+            .is_match(response)
+        {
+            if Regex::new(r"answer is too high")
+                .unwrap()
+                .is_match(response)
+            {
+                Outcome::TooHigh
+            } else if Regex::new(r"answer is too low").unwrap().is_match(response) {
+                Outcome::TooLow
             } else {
-                BadAnswers::Not(num)
+                Outcome::Incorrect
             }
         } else {
-            BadAnswers::Raw(answer.to_owned())
+            Outcome::Correct
         }
     }
 }
 
+/// Answers that are almost certainly a mistake and would only burn a submission cooldown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuspiciousAnswer {
+    Empty,
+    Whitespace,
+    NonAscii,
+    SameAsPartA,
+    UnexpectedNegative,
+}
+
+impl Display for SuspiciousAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuspiciousAnswer::Empty => f.write_str("the answer is empty"),
+            SuspiciousAnswer::Whitespace => f.write_str("the answer contains whitespace"),
+            SuspiciousAnswer::NonAscii => f.write_str("the answer contains non-ASCII characters"),
+            SuspiciousAnswer::SameAsPartA => f.write_str("the answer is the same as Part A's"),
+            SuspiciousAnswer::UnexpectedNegative => {
+                f.write_str("the answer is negative but every previous answer was positive")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubmitMode {
+    #[default]
+    Submit,
+    /// Report what would be submitted without contacting the server.
+    DryRun,
+    /// Show the answer and recent attempts and ask before each submission.
+    Confirm,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SubmitOptions {
+    /// Submit even if the answer looks suspicious.
+    pub force: bool,
+    pub mode: SubmitMode,
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{prompt} [y/n] ");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
+
+/// A single answer that was sent to the server along with what it told us.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum AnswerState {
-    Solution(String),
-    Attempts(Vec<BadAnswers>),
-    PreviouslyDone,
+pub struct Submission {
+    pub answer: String,
+    /// None for submissions migrated from the old state format, which didn't record times.
+    pub timestamp: Option<DateTime<Utc>>,
+    pub outcome: Outcome,
+    pub message: String,
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.timestamp {
+            Some(time) => write!(f, "{} ", time.format("%Y-%m-%d %H:%M:%S"))?,
+            None => f.write_str("unknown time        ")?,
+        }
+        write!(f, "{:?} {:?}", self.answer, self.outcome)
+    }
+}
+
+impl Submission {
+    pub fn new(answer: &str, outcome: Outcome, message: &str) -> Self {
+        Self {
+            answer: answer.to_owned(),
+            timestamp: Some(Utc::now()),
+            outcome,
+            message: message.to_owned(),
+        }
+    }
+
+    /// The constraint this submission places on future answers, if it was rejected.
+    pub fn bad_answer(&self) -> Option<BadAnswers> {
+        let num = self.answer.parse().ok().as_ref().and_then(Answer::as_int);
+        match (self.outcome, num) {
+            (Outcome::Correct | Outcome::AlreadyComplete, _) => None,
+            (Outcome::TooHigh, Some(num)) => Some(BadAnswers::LessThan(num)),
+            (Outcome::TooLow, Some(num)) => Some(BadAnswers::GreaterThan(num)),
+            (_, Some(num)) => Some(BadAnswers::Not(num)),
+            (_, None) => Some(BadAnswers::Raw(self.answer.clone())),
+        }
+    }
+}
+
+/// Append-only submission history for one part of a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "StoredAnswerState")]
+pub struct AnswerState {
+    pub history: Vec<Submission>,
 }
 
 impl AnswerState {
-    pub fn answer_is_ok(&self, answer: &str) -> bool {
-        match self {
-            AnswerState::Solution(expected) => expected == answer,
-            AnswerState::Attempts(prev) => {
-                let num = answer.parse();
-                if let Ok(num) = num {
-                    !prev.iter().any(|x| x.int_is_bad(num))
-                } else {
-                    !prev.iter().any(|x| x.str_is_bad(answer))
-                }
+    pub fn solution(&self) -> Option<&str> {
+        self.history
+            .iter()
+            .find(|x| x.outcome == Outcome::Correct)
+            .map(|x| x.answer.as_str())
+    }
+
+    pub fn bad_answers(&self) -> Vec<BadAnswers> {
+        self.history
+            .iter()
+            .filter_map(Submission::bad_answer)
+            .collect()
+    }
+
+    /// Bounds from earlier attempts only apply to numeric answers.
+    pub fn answer_is_ok(&self, answer: &Answer) -> bool {
+        if let Some(expected) = self.solution() {
+            return expected.parse::<Answer>().ok().as_ref() == Some(answer);
+        }
+        if self.is_solved() {
+            return false;
+        }
+        let prev = self.bad_answers();
+        if let Some(num) = answer.as_int() {
+            !prev.iter().any(|x| x.int_is_bad(num))
+        } else {
+            let answer = answer.to_string();
+            !prev.iter().any(|x| x.str_is_bad(&answer))
+        }
+    }
+
+    /// Checks that don't depend on the server's feedback, only on what the answer looks like.
+    pub fn suspicious(&self, answer: &str) -> Vec<SuspiciousAnswer> {
+        let mut problems = Vec::new();
+        if answer.is_empty() {
+            problems.push(SuspiciousAnswer::Empty);
+        }
+        if answer.chars().any(char::is_whitespace) {
+            problems.push(SuspiciousAnswer::Whitespace);
+        }
+        if !answer.is_ascii() {
+            problems.push(SuspiciousAnswer::NonAscii);
+        }
+        if let Some(num) = answer.parse().ok().as_ref().and_then(Answer::as_int) {
+            let mut previous = self
+                .history
+                .iter()
+                .filter_map(|x| x.answer.parse().ok().as_ref().and_then(Answer::as_int))
+                .peekable();
+            if num < 0 && previous.peek().is_some() && previous.all(|x| x > 0) {
+                problems.push(SuspiciousAnswer::UnexpectedNegative);
             }
-            AnswerState::PreviouslyDone => false,
         }
+        problems
     }
 
     pub fn is_solved(&self) -> bool {
-        match self {
-            AnswerState::Solution(_) => true,
-            AnswerState::Attempts(_) => false,
-            AnswerState::PreviouslyDone => true,
-        }
+        self.history
+            .iter()
+            .any(|x| matches!(x.outcome, Outcome::Correct | Outcome::AlreadyComplete))
     }
 
     pub fn try_submit(
@@ -161,34 +332,89 @@ impl AnswerState {
             return Ok(true);
         }
 
-        if !self.answer_is_ok(answer) {
-            eprintln!("Answer was not valid. {answer} {self:?}");
+        if !self.answer_is_ok(&answer.parse()?) {
+            eprintln!("Answer was not valid. {answer} {:?}", self.bad_answers());
             return Ok(false);
         }
 
         let response = submit_fn(year, day, part, answer)?;
-        let already_done_re = Regex::new(r"already complete").unwrap();
-        if already_done_re.is_match(&response) {
-            *self = AnswerState::PreviouslyDone;
-            return Ok(true);
+        let message = from_read(response.as_bytes(), 120);
+        let outcome = Outcome::from_response(&response);
+        if outcome != Outcome::AlreadyComplete {
+            eprintln!("{message}");
         }
-        eprintln!("{}", from_read(response.as_bytes(), 120));
-        let wrong_re = Regex::new(r"not the right answer")?;
-        if wrong_re.is_match(&response) {
-            if let AnswerState::Attempts(attempts) = self {
-                attempts.push(BadAnswers::from_response(&response, answer));
-            }
-            Ok(false)
+        self.history
+            .push(Submission::new(answer, outcome, &message));
+        Ok(matches!(
+            outcome,
+            Outcome::Correct | Outcome::AlreadyComplete
+        ))
+    }
+}
+
+/// The format `state.json` used before submissions were recorded individually.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LegacyAnswerState {
+    Solution(String),
+    Attempts(Vec<BadAnswers>),
+    PreviouslyDone,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CurrentAnswerState {
+    history: Vec<Submission>,
+}
+
+#[derive(Debug, Clone)]
+enum StoredAnswerState {
+    Current(CurrentAnswerState),
+    Legacy(LegacyAnswerState),
+}
+
+// serde's untagged enums can't buffer i128, so pick the format by hand.
+impl<'de> Deserialize<'de> for StoredAnswerState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let stored = if value.get("history").is_some() {
+            serde_json::from_value(value).map(StoredAnswerState::Current)
         } else {
-            *self = AnswerState::Solution(answer.to_owned());
-            Ok(true)
-        }
+            serde_json::from_value(value).map(StoredAnswerState::Legacy)
+        };
+        stored.map_err(D::Error::custom)
     }
 }
 
-impl Default for AnswerState {
-    fn default() -> Self {
-        AnswerState::Attempts(Vec::new())
+fn migrated(answer: String, outcome: Outcome) -> Submission {
+    Submission {
+        answer,
+        timestamp: None,
+        outcome,
+        message: String::new(),
+    }
+}
+
+impl From<StoredAnswerState> for AnswerState {
+    fn from(stored: StoredAnswerState) -> Self {
+        let history = match stored {
+            StoredAnswerState::Current(CurrentAnswerState { history }) => history,
+            StoredAnswerState::Legacy(LegacyAnswerState::Solution(answer)) => {
+                vec![migrated(answer, Outcome::Correct)]
+            }
+            StoredAnswerState::Legacy(LegacyAnswerState::PreviouslyDone) => {
+                vec![migrated(String::new(), Outcome::AlreadyComplete)]
+            }
+            StoredAnswerState::Legacy(LegacyAnswerState::Attempts(attempts)) => attempts
+                .into_iter()
+                .map(|attempt| match attempt {
+                    BadAnswers::GreaterThan(x) => migrated(x.to_string(), Outcome::TooLow),
+                    BadAnswers::LessThan(x) => migrated(x.to_string(), Outcome::TooHigh),
+                    BadAnswers::Not(x) => migrated(x.to_string(), Outcome::Incorrect),
+                    BadAnswers::Raw(x) => migrated(x, Outcome::Incorrect),
+                })
+                .collect(),
+        };
+        AnswerState { history }
     }
 }
 
@@ -205,48 +431,182 @@ impl PuzzleState {
         year: PuzzleYear,
         day: PuzzleDay,
         answer: &PuzzleAnswer,
+        options: &SubmitOptions,
     ) -> Result<(bool, bool)> {
-        self.try_submit_generic(year, day, answer, |year, day, part, answer| {
+        // Dry runs never touch the server, they go on what's already recorded.
+        if self.open_part().is_some() && options.mode != SubmitMode::DryRun {
+            match read_puzzle(cookie, year, day) {
+                Ok(page) => self.sync_with_page(&page),
+                Err(err) => eprintln!("Unable to check which part is open: {err}"),
+            }
+        }
+        self.try_submit_generic(year, day, answer, options, |year, day, part, answer| {
             submit_answer(cookie, year, day, part, answer)
         })
     }
 
+    /// The part that the server will currently accept an answer for, None once both are solved.
+    pub fn open_part(&self) -> Option<Part> {
+        if !self.part_a.is_solved() {
+            Some(Part::A)
+        } else if !self.part_b.is_solved() {
+            Some(Part::B)
+        } else {
+            None
+        }
+    }
+
+    /// Records any answers the puzzle page shows as accepted, e.g. ones submitted from the browser.
+    pub fn sync_with_page(&mut self, page: &str) {
+        let accepted =
+            Regex::new(r"Your puzzle answer was <code>(?P<answer>[^<]*)</code>").unwrap();
+        let answers = accepted.captures_iter(page).map(|x| x["answer"].to_owned());
+        for (state, answer) in [&mut self.part_a, &mut self.part_b]
+            .into_iter()
+            .zip(answers)
+        {
+            if !state.is_solved() {
+                state.history.push(Submission::new(
+                    &answer,
+                    Outcome::Correct,
+                    "Found on the puzzle page",
+                ));
+            }
+        }
+    }
+
+    pub fn suspicious(
+        &self,
+        part: Part,
+        answer: &str,
+        part_a: Option<&str>,
+    ) -> Vec<SuspiciousAnswer> {
+        let mut problems = self.part(part).suspicious(answer);
+        if let Part::B = part {
+            if self.part_a.solution().or(part_a) == Some(answer) {
+                problems.push(SuspiciousAnswer::SameAsPartA);
+            }
+        }
+        problems
+    }
+
     pub fn try_submit_generic(
         &mut self,
         year: PuzzleYear,
         day: PuzzleDay,
         answer: &PuzzleAnswer,
+        options: &SubmitOptions,
         submit_fn: impl Fn(PuzzleYear, PuzzleDay, Part, &str) -> Result<String>,
     ) -> Result<(bool, bool)> {
-        let PuzzleAnswer(a, b) = answer;
+        let a = answer.0.as_ref();
+        let b = answer.1.as_ref();
         let a_correct = if let Some(answer_a) = a {
-            self.part_a
-                .try_submit(year, day, Part::A, answer_a, &submit_fn)?
+            self.try_submit_part(year, day, Part::A, answer_a, None, options, &submit_fn)?
         } else {
             false
         };
 
-        let b_correct = if let Some(answer_b) = b {
-            self.part_b
-                .try_submit(year, day, Part::B, answer_b, &submit_fn)?
-        } else {
-            false
+        let b_correct = match b {
+            Some(answer_b) if self.part_a.is_solved() => {
+                self.try_submit_part(year, day, Part::B, answer_b, a, options, &submit_fn)?
+            }
+            Some(answer_b) => {
+                eprintln!("Not submitting part B answer {answer_b:?}, part A isn't solved yet.");
+                false
+            }
+            None => false,
         };
 
         Ok((a_correct, b_correct))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn try_submit_part(
+        &mut self,
+        year: PuzzleYear,
+        day: PuzzleDay,
+        part: Part,
+        answer: &Answer,
+        part_a: Option<&Answer>,
+        options: &SubmitOptions,
+        submit_fn: impl Fn(PuzzleYear, PuzzleDay, Part, &str) -> Result<String>,
+    ) -> Result<bool> {
+        let state = self.part(part);
+        let solved = state.is_solved();
+        let allowed = state.answer_is_ok(answer);
+        let part_a = part_a.map(Answer::to_string);
+        let answer = &answer.to_string();
+        let problems = if solved {
+            Vec::new()
+        } else {
+            self.suspicious(part, answer, part_a.as_deref())
+        };
+        for problem in &problems {
+            eprintln!("Part {part:?} answer {answer:?} looks wrong: {problem}");
+        }
+        let refused = !problems.is_empty() && !options.force;
+
+        match options.mode {
+            SubmitMode::DryRun => {
+                if solved {
+                    eprintln!(
+                        "Part {part:?} is already solved, {answer:?} would not be submitted."
+                    );
+                } else if refused {
+                    eprintln!("Part {part:?} answer {answer:?} would be refused without --force.");
+                } else if !allowed {
+                    eprintln!(
+                        "Part {part:?} answer {answer:?} would be rejected by previous attempts {:?}",
+                        state.bad_answers()
+                    );
+                } else {
+                    eprintln!("Would submit {answer:?} for part {part:?}.");
+                }
+                return Ok(solved);
+            }
+            SubmitMode::Confirm if !solved && !refused && allowed => {
+                eprintln!("Part {part:?} answer: {answer}");
+                for attempt in state.history.iter().rev().take(5) {
+                    eprintln!("  {attempt}");
+                }
+                if !confirm("Submit?")? {
+                    return Ok(false);
+                }
+            }
+            _ => (),
+        }
+
+        if refused {
+            eprintln!("Refusing to submit, pass --force to submit anyway.");
+            return Ok(false);
+        }
+
+        let state = match part {
+            Part::A => &mut self.part_a,
+            Part::B => &mut self.part_b,
+        };
+        state.try_submit(year, day, part, answer, submit_fn)
+    }
+
     pub fn load(year: PuzzleYear, day: PuzzleDay) -> Self {
         let dir = get_puzzle_dir(year, day);
         let path = dir.join("state.json");
         if let Ok(contents) = fs::read_to_string(path) {
-            if let Ok(val) = serde_json::from_str(&contents) {
-                return val;
-            };
+            match serde_json::from_str(&contents) {
+                Ok(val) => return val,
+                Err(err) => eprintln!("Unable to read state for {year} day {day}: {err}"),
+            }
         };
         Default::default()
     }
 
+    pub fn part(&self, part: Part) -> &AnswerState {
+        match part {
+            Part::A => &self.part_a,
+            Part::B => &self.part_b,
+        }
+    }
+
     pub fn save(&self, year: PuzzleYear, day: PuzzleDay) -> Result<()> {
         let path = get_puzzle_dir(year, day).join("state.json");
         let contents = serde_json::to_string_pretty(self)?;
@@ -254,3 +614,179 @@ impl PuzzleState {
         Ok(())
     }
 }
+
+#[test]
+pub fn migrate_legacy_state() {
+    let state: PuzzleState = serde_json::from_str(
+        r#"{
+            "part_a": { "solution": "10064" },
+            "part_b": { "attempts": [{ "lessthan": 500 }, { "greaterthan": 100 }, { "raw": "abc" }] }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(state.part(Part::A).solution(), Some("10064"));
+    assert!(state.part(Part::A).answer_is_ok(&Answer::Integer(10064)));
+    assert!(!state.part(Part::A).answer_is_ok(&Answer::Integer(10065)));
+
+    let part_b = state.part(Part::B);
+    let ok = |answer: &str| part_b.answer_is_ok(&answer.parse().unwrap());
+    assert!(!part_b.is_solved());
+    assert!(!ok("500"));
+    assert!(!ok("100"));
+    assert!(!ok("abc"));
+    assert!(ok("250"));
+    assert!(ok("abcd"));
+
+    let done: PuzzleState =
+        serde_json::from_str(r#"{ "part_a": "previouslydone", "part_b": "previouslydone" }"#)
+            .unwrap();
+    assert!(done.part(Part::A).is_solved());
+
+    let round_trip: PuzzleState =
+        serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+    assert_eq!(round_trip.part(Part::B), state.part(Part::B));
+}
+
+#[test]
+pub fn history_records_submissions() {
+    let year = PuzzleYear::try_from(2022).unwrap();
+    let day = PuzzleDay::try_from(1).unwrap();
+    let mut state = AnswerState::default();
+    let res = state
+        .try_submit(year, day, Part::A, "42", |_, _, _, _| {
+            Ok("That's not the right answer; your answer is too high.".to_owned())
+        })
+        .unwrap();
+    assert!(!res);
+    assert_eq!(state.bad_answers(), vec![BadAnswers::LessThan(42)]);
+    assert!(!state.answer_is_ok(&Answer::Integer(43)));
+
+    let res = state
+        .try_submit(year, day, Part::A, "7", |_, _, _, _| {
+            Ok("That's the right answer!".to_owned())
+        })
+        .unwrap();
+    assert!(res);
+    assert_eq!(state.history.len(), 2);
+    assert_eq!(state.solution(), Some("7"));
+}
+
+#[test]
+pub fn numeric_text_respects_bounds() {
+    let year = PuzzleYear::try_from(2022).unwrap();
+    let day = PuzzleDay::try_from(1).unwrap();
+    let mut state = PuzzleState::default();
+    state.part_a.history.push(Submission::new(
+        "4217",
+        Outcome::TooHigh,
+        "your answer is too high",
+    ));
+    let answer: PuzzleAnswer = String::from("5000").into();
+    assert_eq!(answer.0, Some(Answer::Integer(5000)));
+    let res = state
+        .try_submit_generic(
+            year,
+            day,
+            &answer,
+            &SubmitOptions::default(),
+            |_, _, _, _| panic!("5000 is already known to be too high"),
+        )
+        .unwrap();
+    assert_eq!(res, (false, false));
+}
+
+#[test]
+pub fn refuses_suspicious_answers() {
+    let year = PuzzleYear::try_from(2022).unwrap();
+    let day = PuzzleDay::try_from(1).unwrap();
+    let mut state = PuzzleState::default();
+    let answer = PuzzleAnswer(Some(Answer::Integer(12)), Some("12".into()));
+    let submit = |_, _, part: Part, _: &str| match part {
+        Part::A => Ok("That's the right answer!".to_owned()),
+        Part::B => panic!("Part B should not have been submitted"),
+    };
+    let res = state
+        .try_submit_generic(year, day, &answer, &SubmitOptions::default(), submit)
+        .unwrap();
+    assert_eq!(res, (true, false));
+
+    assert_eq!(
+        state.suspicious(Part::A, " 12\n", None),
+        vec![SuspiciousAnswer::Whitespace]
+    );
+    assert_eq!(
+        state.suspicious(Part::A, "", None),
+        vec![SuspiciousAnswer::Empty]
+    );
+    assert_eq!(
+        state.suspicious(Part::B, "-12", None),
+        Vec::<SuspiciousAnswer>::new()
+    );
+    assert_eq!(
+        state.part(Part::A).suspicious("-12"),
+        vec![SuspiciousAnswer::UnexpectedNegative]
+    );
+    assert_eq!(
+        state.suspicious(Part::B, "ünicode", None),
+        vec![SuspiciousAnswer::NonAscii]
+    );
+
+    let forced = SubmitOptions {
+        force: true,
+        ..Default::default()
+    };
+    let res = state
+        .try_submit_generic(year, day, &answer, &forced, |_, _, _, _| {
+            Ok("That's not the right answer.".to_owned())
+        })
+        .unwrap();
+    assert_eq!(res, (true, false));
+    assert_eq!(state.part(Part::B).history.len(), 1);
+}
+
+#[test]
+pub fn dry_run_never_submits() {
+    let year = PuzzleYear::try_from(2022).unwrap();
+    let day = PuzzleDay::try_from(1).unwrap();
+    let mut state = PuzzleState::default();
+    let options = SubmitOptions {
+        mode: SubmitMode::DryRun,
+        ..Default::default()
+    };
+    let answer = PuzzleAnswer(Some(Answer::Integer(12)), Some("\n".into()));
+    let res = state
+        .try_submit_generic(year, day, &answer, &options, |_, _, _, _| {
+            panic!("dry runs should not submit")
+        })
+        .unwrap();
+    assert_eq!(res, (false, false));
+    assert!(state.part(Part::A).history.is_empty());
+}
+
+#[test]
+pub fn only_submits_open_part() {
+    let year = PuzzleYear::try_from(2022).unwrap();
+    let day = PuzzleDay::try_from(1).unwrap();
+    let mut state = PuzzleState::default();
+    assert_eq!(state.open_part(), Some(Part::A));
+
+    let answer = PuzzleAnswer(Some("10 \n".into()), Some(Answer::Integer(20)));
+    let res = state
+        .try_submit_generic(
+            year,
+            day,
+            &answer,
+            &SubmitOptions::default(),
+            |_, _, part, answer| {
+                assert_eq!((part, answer), (Part::A, "10"));
+                Ok("That's not the right answer.".to_owned())
+            },
+        )
+        .unwrap();
+    assert_eq!(res, (false, false));
+    assert!(state.part(Part::B).history.is_empty());
+
+    state.sync_with_page("<p>Your puzzle answer was <code>11</code>.</p><h2>--- Part Two ---</h2>");
+    assert_eq!(state.part(Part::A).solution(), Some("11"));
+    assert_eq!(state.open_part(), Some(Part::B));
+}
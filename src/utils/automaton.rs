@@ -0,0 +1,230 @@
+use super::{get_neighbors, Grid};
+use glam::IVec2;
+use hashbrown::HashMap;
+use rayon::prelude::*;
+use smallvec::SmallVec;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// What a cell sees past the edge of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// The background value, the board never grows.
+    Bounded,
+    /// The opposite side of the board.
+    Wrap,
+    /// The background value, but the board grows by a cell on every side each step and the
+    /// background itself evolves as if it were a cell surrounded by background.
+    Infinite,
+}
+
+/// A rule gets a cell and the values of its neighborhood, in the order of the offsets.
+pub type Rule<'a, T> = &'a (dyn Fn(&T, &[T]) -> T + Sync);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The step the repeating state was first seen after.
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Steps a board of cells, every cell of a step is updated from the previous state so rows
+/// can be computed in parallel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Automaton<T> {
+    pub board: Grid<T>,
+    pub background: T,
+    pub edges: Edges,
+    offsets: Vec<IVec2>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync> Automaton<T> {
+    /// Cells see their eight neighbors in reading order, like `get_neighbors`.
+    pub fn new(board: Grid<T>, background: T, edges: Edges) -> Self {
+        Automaton {
+            board,
+            background,
+            edges,
+            offsets: get_neighbors(IVec2::ZERO).to_vec(),
+        }
+    }
+
+    /// Offsets of the cells a rule is given, `get_cardinal_neighbors(IVec2::ZERO)` for
+    /// example, or `get_neighbors_and_pos` to include the cell itself.
+    pub fn with_neighborhood(mut self, offsets: &[IVec2]) -> Self {
+        self.offsets = offsets.to_vec();
+        self
+    }
+
+    /// Positions past the edge follow `edges`, an empty board only has the background.
+    pub fn get(&self, pos: IVec2) -> &T {
+        match self.edges {
+            Edges::Wrap if self.board.width() > 0 && self.board.height() > 0 => {
+                let size = IVec2::new(self.board.width() as i32, self.board.height() as i32);
+                let origin = self.board.origin();
+                let rel = pos - origin;
+                &self.board[origin + IVec2::new(rel.x.rem_euclid(size.x), rel.y.rem_euclid(size.y))]
+            }
+            _ => self.board.get(pos).unwrap_or(&self.background),
+        }
+    }
+
+    fn grow(&mut self) {
+        let width = self.board.width() + 2;
+        let height = self.board.height() + 2;
+        let origin = self.board.origin() - IVec2::ONE;
+        let board = &self.board;
+        let background = &self.background;
+        self.board = Grid::from_fn(width, height, |rel| {
+            board.get(origin + rel).unwrap_or(background).clone()
+        })
+        .with_origin(origin);
+    }
+
+    /// Applies the rule to every cell at once, returning whether anything changed.
+    pub fn step(&mut self, rule: Rule<T>) -> bool {
+        self.step_ordered(&[rule])
+    }
+
+    /// Runs one step as a sequence of phases, each seeing the result of the one before, like
+    /// herds that take turns to move. An infinite board only grows once per step.
+    pub fn step_ordered(&mut self, phases: &[Rule<T>]) -> bool {
+        if self.edges == Edges::Infinite {
+            self.grow();
+        }
+        phases
+            .iter()
+            .fold(false, |changed, phase| self.apply(*phase) | changed)
+    }
+
+    /// One phase of a step without growing, the background evolves alongside the board so the
+    /// next phase sees both at the same point.
+    fn apply(&mut self, rule: Rule<T>) -> bool {
+        let origin = self.board.origin();
+        let rows: Vec<Vec<T>> = (0..self.board.height() as i32)
+            .into_par_iter()
+            .map(|y| {
+                (0..self.board.width() as i32)
+                    .map(|x| {
+                        let pos = origin + IVec2::new(x, y);
+                        let neighborhood: SmallVec<[T; 9]> = self
+                            .offsets
+                            .iter()
+                            .map(|offset| self.get(pos + *offset).clone())
+                            .collect();
+                        rule(&self.board[pos], &neighborhood)
+                    })
+                    .collect()
+            })
+            .collect();
+        // Every row was built from the same width so this can't fail.
+        let board = Grid::from_rows(rows).unwrap().with_origin(origin);
+        let mut changed = board != self.board;
+        self.board = board;
+        if self.edges == Edges::Infinite {
+            let surrounded = vec![self.background.clone(); self.offsets.len()];
+            let background = rule(&self.background, &surrounded);
+            changed |= background != self.background;
+            self.background = background;
+        }
+        changed
+    }
+
+    /// Steps until nothing changes, returning how many steps it took including the last one.
+    /// Gives up with `None` after `limit` steps.
+    pub fn run_until_stable(
+        &mut self,
+        limit: usize,
+        mut step: impl FnMut(&mut Self) -> bool,
+    ) -> Option<usize> {
+        (1..=limit).find(|_| !step(self))
+    }
+
+    /// Steps until a state repeats, the automaton is left at the first repeat. Every state is
+    /// kept so a hash collision can't be mistaken for a cycle.
+    pub fn find_cycle(
+        &mut self,
+        limit: usize,
+        mut step: impl FnMut(&mut Self) -> bool,
+    ) -> Option<Cycle>
+    where
+        T: Hash,
+    {
+        let mut seen: HashMap<u64, Vec<(usize, Grid<T>, T)>> = HashMap::new();
+        for i in 0..=limit {
+            if i > 0 {
+                step(self);
+            }
+            let states = seen.entry(self.state_hash()).or_default();
+            let repeat = states.iter().find(|(_, board, background)| {
+                *board == self.board && *background == self.background
+            });
+            if let Some((start, _, _)) = repeat {
+                return Some(Cycle {
+                    start: *start,
+                    length: i - start,
+                });
+            }
+            states.push((i, self.board.clone(), self.background.clone()));
+        }
+        None
+    }
+
+    fn state_hash(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        (self.board.width(), self.board.origin().to_array()).hash(&mut hasher);
+        self.background.hash(&mut hasher);
+        for (_, cell) in self.board.iter() {
+            cell.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+#[test]
+pub fn blinker_cycles() {
+    let board = Grid::from_fn(5, 5, |pos| pos.y == 2 && (1..=3).contains(&pos.x));
+    let life = |alive: &bool, neighbors: &[bool]| {
+        let count = neighbors.iter().filter(|n| **n).count();
+        count == 3 || (*alive && count == 2)
+    };
+    let mut automaton = Automaton::new(board, false, Edges::Bounded);
+    let cycle = automaton.find_cycle(10, |a| a.step(&life));
+    assert_eq!(
+        cycle,
+        Some(Cycle {
+            start: 0,
+            length: 2
+        })
+    );
+
+    // A glider on a wrapping board comes back to where it started.
+    let glider = Grid::from_fn(6, 6, |pos| {
+        [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].contains(&(pos.x, pos.y))
+    });
+    let mut automaton = Automaton::new(glider.clone(), false, Edges::Wrap);
+    for _ in 0..24 {
+        automaton.step(&life);
+    }
+    assert_eq!(automaton.board, glider);
+
+    // Everything lights up next to a lit cell, including the infinite background once a rule
+    // turns an empty neighborhood on.
+    let spread = |lit: &bool, neighbors: &[bool]| *lit || neighbors.iter().any(|n| *n);
+    let mut automaton = Automaton::new(Grid::new(1, 1, true), false, Edges::Infinite);
+    assert_eq!(automaton.run_until_stable(5, |a| a.step(&spread)), None);
+    assert_eq!(automaton.board.width(), 11);
+    assert!(automaton.board.iter().all(|(_, lit)| *lit));
+    let invert = |lit: &bool, _: &[bool]| !lit;
+    automaton.step(&invert);
+    assert!(automaton.background);
+    automaton.step_ordered(&[&invert, &invert]);
+    assert_eq!(automaton.board.width(), 15);
+    assert!(automaton.background);
+
+    let empty = Automaton::new(Grid::new(0, 0, false), true, Edges::Wrap);
+    assert!(*empty.get(IVec2::new(3, -2)));
+}
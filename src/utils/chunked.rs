@@ -0,0 +1,126 @@
+use super::GridLike;
+use glam::IVec2;
+use hashbrown::HashMap;
+
+const CHUNK_SIZE: i32 = 32;
+
+/// An unbounded grid made of square tiles that are only allocated once something is written to
+/// them. Cells that were never written read as the background value.
+#[derive(Debug, Clone)]
+pub struct ChunkedGrid<T> {
+    chunks: HashMap<IVec2, Vec<T>>,
+    background: T,
+    bounds: Option<(IVec2, IVec2)>,
+}
+
+fn split(pos: IVec2) -> (IVec2, usize) {
+    let chunk = IVec2::new(pos.x.div_euclid(CHUNK_SIZE), pos.y.div_euclid(CHUNK_SIZE));
+    let local = pos - chunk * CHUNK_SIZE;
+    (chunk, (local.y * CHUNK_SIZE + local.x) as usize)
+}
+
+impl<T: Clone> ChunkedGrid<T> {
+    pub fn new() -> Self
+    where
+        T: Default,
+    {
+        Self::with_background(T::default())
+    }
+
+    pub fn with_background(background: T) -> Self {
+        ChunkedGrid {
+            chunks: HashMap::new(),
+            background,
+            bounds: None,
+        }
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, pos: IVec2) -> &T {
+        let (chunk, i) = split(pos);
+        self.chunks
+            .get(&chunk)
+            .map_or(&self.background, |cells| &cells[i])
+    }
+
+    /// Allocates the tile holding `pos` if it doesn't exist yet.
+    pub fn get_mut(&mut self, pos: IVec2) -> &mut T {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(pos), max.max(pos)),
+            None => (pos, pos),
+        });
+        let (chunk, i) = split(pos);
+        let background = &self.background;
+        let cells = self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| vec![background.clone(); (CHUNK_SIZE * CHUNK_SIZE) as usize]);
+        &mut cells[i]
+    }
+
+    pub fn set(&mut self, pos: IVec2, val: T) {
+        *self.get_mut(pos) = val;
+    }
+
+    /// The inclusive corners of every cell that has been written, `None` when nothing has.
+    pub fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        self.bounds
+    }
+
+    /// The cells of every allocated tile that fall inside the bounds, in no particular order.
+    /// Unallocated areas are skipped even when they are inside the bounds.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        let (min, max) = self.bounds.unwrap_or((IVec2::ONE, IVec2::ZERO));
+        self.chunks
+            .iter()
+            .flat_map(|(chunk, cells)| {
+                cells.iter().enumerate().map(move |(i, val)| {
+                    let local = IVec2::new(i as i32 % CHUNK_SIZE, i as i32 / CHUNK_SIZE);
+                    (*chunk * CHUNK_SIZE + local, val)
+                })
+            })
+            .filter(move |(pos, _)| pos.cmpge(min).all() && pos.cmple(max).all())
+    }
+}
+
+impl<T: Clone + Default> Default for ChunkedGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> GridLike for ChunkedGrid<T> {
+    type Cell = T;
+
+    fn get(&self, pos: IVec2) -> Option<&T> {
+        Some(ChunkedGrid::get(self, pos))
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        self.bounds
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        ChunkedGrid::iter(self)
+    }
+}
+
+#[test]
+pub fn chunked_grid() {
+    let mut grid = ChunkedGrid::with_background('.');
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(*grid.get(IVec2::new(-1000, 5)), '.');
+    grid.set(IVec2::new(-1, -1), '#');
+    grid.set(IVec2::new(40, 2), '#');
+    *grid.get_mut(IVec2::new(0, 0)) = '@';
+    assert_eq!(*grid.get(IVec2::new(-1, -1)), '#');
+    assert_eq!(*grid.get(IVec2::ZERO), '@');
+    assert_eq!(*grid.get(IVec2::new(-2, -1)), '.');
+    assert_eq!(grid.bounds(), Some((IVec2::new(-1, -1), IVec2::new(40, 2))));
+    assert_eq!(grid.chunks.len(), 3);
+    assert_eq!(grid.iter().count(), 1 + 32 * 3 + 9 * 3);
+    assert_eq!(grid.iter().filter(|(_, c)| **c != '.').count(), 3);
+}
@@ -0,0 +1,189 @@
+use std::{convert::TryFrom, time::Duration};
+
+use super::{
+    aocdata::{Part, SubmitMode, SubmitOptions},
+    puzzle::{PuzzleDay, PuzzleYear, Verbosity},
+};
+use anyhow::{anyhow, Context, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Run whatever main is currently hard-coded to work on.
+    Default,
+    Submit {
+        year: PuzzleYear,
+        day: PuzzleDay,
+        part: Part,
+        answer: String,
+    },
+    /// Run a registered solver on the real input and submit its answers.
+    Solve { puzzle: (PuzzleYear, PuzzleDay) },
+    /// Run the example test cases of one puzzle, or of every registered puzzle.
+    Test {
+        puzzle: Option<(PuzzleYear, PuzzleDay)>,
+    },
+    /// Solve every registered puzzle on its real input without submitting.
+    RunAll,
+    /// Time the solvers of one puzzle, or of every registered puzzle, over several runs.
+    Bench {
+        puzzle: Option<(PuzzleYear, PuzzleDay)>,
+        iterations: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Args {
+    pub command: Command,
+    pub options: SubmitOptions,
+    /// Overrides the verbosity the command would otherwise run solvers with.
+    pub verbosity: Option<Verbosity>,
+    /// How long run-all and bench wait for each solver, in seconds on the command line.
+    pub timeout: Option<Duration>,
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<&'a str> {
+    let idx = args
+        .iter()
+        .position(|arg| arg == flag)
+        .ok_or_else(|| anyhow!("Missing {flag}"))?;
+    args.get(idx + 1)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("Missing value for {flag}"))
+}
+
+fn puzzle_id(args: &[String]) -> Result<(PuzzleYear, PuzzleDay)> {
+    let year: i32 = flag_value(args, "--year")?
+        .parse()
+        .context("Parsing --year")?;
+    let day: u32 = flag_value(args, "--day")?
+        .parse()
+        .context("Parsing --day")?;
+    Ok((PuzzleYear::try_from(year)?, PuzzleDay::try_from(day)?))
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let args: Vec<String> = args.into_iter().collect();
+        let has_flag = |flag: &str| args.iter().any(|arg| arg == flag);
+        let mode = if has_flag("--dry-run") {
+            SubmitMode::DryRun
+        } else if has_flag("--confirm") {
+            SubmitMode::Confirm
+        } else {
+            SubmitMode::Submit
+        };
+        let options = SubmitOptions {
+            force: has_flag("--force"),
+            mode,
+        };
+        let verbosity = if has_flag("--debug") {
+            Some(Verbosity::Debug)
+        } else if has_flag("--verbose") {
+            Some(Verbosity::Info)
+        } else if has_flag("--quiet") {
+            Some(Verbosity::Quiet)
+        } else {
+            None
+        };
+        let timeout = if has_flag("--timeout") {
+            let secs: f64 = flag_value(&args, "--timeout")?
+                .parse()
+                .context("Parsing --timeout")?;
+            Some(
+                Duration::try_from_secs_f64(secs)
+                    .map_err(|_| anyhow!("--timeout must be a non-negative number of seconds"))?,
+            )
+        } else {
+            None
+        };
+        let puzzle = if has_flag("--year") || has_flag("--day") {
+            Some(puzzle_id(&args)?)
+        } else {
+            None
+        };
+        let command = match args.first().map(String::as_str) {
+            Some("submit") => {
+                let (year, day) = puzzle_id(&args)?;
+                Command::Submit {
+                    year,
+                    day,
+                    part: flag_value(&args, "--part")?.parse()?,
+                    answer: flag_value(&args, "--answer")?.to_owned(),
+                }
+            }
+            Some("solve") => Command::Solve {
+                puzzle: puzzle_id(&args)?,
+            },
+            Some("test") => Command::Test { puzzle },
+            Some("run-all") => Command::RunAll,
+            Some("bench") => Command::Bench {
+                puzzle,
+                iterations: if has_flag("--iterations") {
+                    flag_value(&args, "--iterations")?
+                        .parse()
+                        .context("Parsing --iterations")?
+                } else {
+                    10
+                },
+            },
+            _ => Command::Default,
+        };
+        Ok(Args {
+            command,
+            options,
+            verbosity,
+            timeout,
+        })
+    }
+}
+
+#[test]
+pub fn parse_submit() {
+    let args = Args::parse(
+        "submit --year 2023 --day 5 --part b --answer 46 --force"
+            .split(' ')
+            .map(str::to_owned),
+    )
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Command::Submit {
+            year: PuzzleYear::try_from(2023).unwrap(),
+            day: PuzzleDay::try_from(5).unwrap(),
+            part: Part::B,
+            answer: "46".to_owned(),
+        }
+    );
+    assert!(args.options.force);
+    assert_eq!(args.options.mode, SubmitMode::Submit);
+
+    let args = Args::parse(["--dry-run".to_owned()]).unwrap();
+    assert_eq!(args.options.mode, SubmitMode::DryRun);
+
+    assert!(Args::parse(["submit".to_owned(), "--year".to_owned()]).is_err());
+    assert_eq!(Args::parse(Vec::new()).unwrap().command, Command::Default);
+    assert_eq!(
+        Args::parse(["test".to_owned()]).unwrap().command,
+        Command::Test { puzzle: None }
+    );
+
+    let args = Args::parse(
+        "bench --iterations 3 --debug --timeout 1.5"
+            .split(' ')
+            .map(str::to_owned),
+    )
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Command::Bench {
+            puzzle: None,
+            iterations: 3
+        }
+    );
+    assert_eq!(args.verbosity, Some(Verbosity::Debug));
+    assert_eq!(args.timeout, Some(Duration::from_millis(1500)));
+    for timeout in ["-1", "NaN"] {
+        let args = ["run-all", "--timeout", timeout].map(str::to_owned);
+        assert!(Args::parse(args).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -14,7 +14,7 @@ impl SolvePuzzle for AoC2023Day1 {
         (2023, 1)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
 
         Ok((None, None))
     }
@@ -27,10 +27,4 @@ impl SolvePuzzle for AoC2023Day1 {
     }
 }
 
-#[test]
-fn run() -> Result<()> {
-    AoC2023Day1::run_tests()?;
-    let res = AoC2023Day1::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day1, 2023, 1);
@@ -0,0 +1,573 @@
+use super::{
+    djikstra, djikstra_path, get_cardinal_neighbors, get_neighbors, Axis, Rotation, World,
+};
+use anyhow::{anyhow, Result};
+use glam::IVec2;
+use hashbrown::HashSet;
+use itertools::Itertools;
+use smallvec::SmallVec;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// Lookups shared by the sparse `World` and the dense `Grid` so the search and printing
+/// helpers work on either.
+pub trait GridLike {
+    type Cell;
+
+    fn get(&self, pos: IVec2) -> Option<&Self::Cell>;
+
+    /// The inclusive min and max corners of the occupied cells, `None` when there are none.
+    fn bounds(&self) -> Option<(IVec2, IVec2)>;
+
+    /// Every occupied cell, the order depends on the storage.
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &Self::Cell)> + '_;
+
+    fn in_bounds(&self, pos: IVec2) -> bool {
+        self.get(pos).is_some()
+    }
+
+    /// The cardinal neighbors of `pos` that are in bounds.
+    fn neighbors4(&self, pos: IVec2) -> SmallVec<[IVec2; 4]> {
+        get_cardinal_neighbors(pos)
+            .into_iter()
+            .filter(|n| self.in_bounds(*n))
+            .collect()
+    }
+
+    /// All eight neighbors of `pos` that are in bounds.
+    fn neighbors8(&self, pos: IVec2) -> SmallVec<[IVec2; 8]> {
+        get_neighbors(pos)
+            .into_iter()
+            .filter(|n| self.in_bounds(*n))
+            .collect()
+    }
+
+    fn max_x(&self) -> i32 {
+        self.bounds().unwrap().1.x
+    }
+    fn max_y(&self) -> i32 {
+        self.bounds().unwrap().1.y
+    }
+
+    fn min_x(&self) -> i32 {
+        self.bounds().unwrap().0.x
+    }
+    fn min_y(&self) -> i32 {
+        self.bounds().unwrap().0.y
+    }
+
+    fn pretty_print(&self, rev_y: bool)
+    where
+        Self::Cell: Display,
+    {
+        self.pretty_print_custom(|x, _| x.to_string(), rev_y)
+    }
+
+    fn pretty_print_custom(&self, str_fn: impl Fn(&Self::Cell, IVec2) -> String, rev_y: bool) {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let min = min.min(IVec2::ZERO);
+        let rows = min.y..=max.y;
+        let rows: Box<dyn Iterator<Item = i32>> = if rev_y {
+            Box::new(rows.rev())
+        } else {
+            Box::new(rows)
+        };
+        for y in rows {
+            let row = (min.x..=max.x)
+                .map(|x| {
+                    let pos = IVec2::new(x, y);
+                    match self.get(pos) {
+                        Some(val) => str_fn(val, pos),
+                        None => " ".to_string(),
+                    }
+                })
+                .join("");
+            println!("{}", row);
+        }
+    }
+
+    /***
+     * Calculates the minimum cost to move from the start position to the state determined by the done fn.
+     * If done never returns true this function returns None. The weight function should return None for
+     * any position that cannot be traversed.
+     */
+    fn min_cost_4<S>(
+        &mut self,
+        state: &mut S,
+        start: IVec2,
+        done: impl Fn(IVec2, &mut Self, &mut S, usize) -> bool,
+        get_weight: impl Fn(IVec2, &mut Self, &mut S) -> Option<usize>,
+    ) -> Option<usize>
+    where
+        Self: Sized,
+    {
+        djikstra(
+            start,
+            0,
+            &mut (self, state),
+            |&pos, cost, (world, extra_state)| done(pos, world, extra_state, cost),
+            |pos, _, (world, extra_state)| {
+                let x: SmallVec<[_; 4]> = get_cardinal_neighbors(*pos)
+                    .iter()
+                    .map(|x| get_weight(*x, world, extra_state).map(|w| (*x, w)))
+                    .collect();
+                x
+            },
+        )
+    }
+
+    /***
+     * Calculates the lowest cost path to move from the start position to the state determined by the done fn.
+     * If done never returns true this function returns None. The weight function should return None for
+     * any position that cannot be traversed.
+     */
+    fn min_path_4<S>(
+        &mut self,
+        extra_state: &mut S,
+        start: IVec2,
+        done: impl Fn(IVec2, &mut Self, &mut S) -> bool,
+        get_weight: impl Fn(IVec2, &mut Self, &mut S) -> Option<usize>,
+    ) -> Option<(usize, Vec<IVec2>)>
+    where
+        Self: Sized,
+    {
+        djikstra_path(
+            start,
+            0,
+            &mut (self, extra_state),
+            |&pos, _, (state, extra_state)| done(pos, state, extra_state),
+            |pos, _, (state, extra_state)| {
+                let x: SmallVec<[_; 4]> = get_cardinal_neighbors(*pos)
+                    .iter()
+                    .map(|x| get_weight(*x, state, extra_state).map(|w| (*x, w)))
+                    .collect();
+                x
+            },
+        )
+    }
+}
+
+/// A dense rectangle of cells stored row by row, `origin` is the position of the first cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    origin: IVec2,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            origin: IVec2::ZERO,
+            data: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(IVec2) -> T) -> Self {
+        let data = (0..height)
+            .cartesian_product(0..width)
+            .map(|(y, x)| f(IVec2::new(x as i32, y as i32)))
+            .collect();
+        Grid {
+            width,
+            height,
+            origin: IVec2::ZERO,
+            data,
+        }
+    }
+
+    /// Fails if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(anyhow!(
+                "Row {y} has {} cells, expected {width}",
+                rows[y].len()
+            ));
+        }
+        Ok(Grid {
+            width,
+            height,
+            origin: IVec2::ZERO,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Every cell missing from the world is filled in with `fill`.
+    pub fn from_world(world: &World<T>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let (min, max) = match world.bounds() {
+            Some(bounds) => bounds,
+            None => return Grid::new(0, 0, fill),
+        };
+        let size = max - min + IVec2::ONE;
+        let mut grid = Grid::new(size.x as usize, size.y as usize, fill).with_origin(min);
+        for (pos, val) in &world.world {
+            grid[*pos] = val.clone();
+        }
+        grid
+    }
+
+    pub fn with_origin(mut self, origin: IVec2) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn origin(&self) -> IVec2 {
+        self.origin
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        let rel = pos - self.origin;
+        if rel.x < 0 || rel.y < 0 || rel.x as usize >= self.width || rel.y as usize >= self.height {
+            None
+        } else {
+            Some(rel.y as usize * self.width + rel.x as usize)
+        }
+    }
+
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.data[i])
+    }
+
+    pub fn row(&self, y: i32) -> &[T] {
+        let rel = y - self.origin.y;
+        assert!(
+            (0..self.height as i32).contains(&rel),
+            "Row {y} is outside the grid"
+        );
+        let start = rel as usize * self.width;
+        &self.data[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // chunks panics on a zero size, an empty grid simply has no rows.
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> + '_ {
+        let rel = x - self.origin.x;
+        assert!(
+            (0..self.width as i32).contains(&rel),
+            "Column {x} is outside the grid"
+        );
+        self.data.iter().skip(rel as usize).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width as i32).map(move |x| self.column(x + self.origin.x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + '_ {
+        (0..self.height as i32)
+            .cartesian_product(0..self.width as i32)
+            .map(move |(y, x)| IVec2::new(x, y) + self.origin)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            origin: self.origin,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+}
+
+/// The transforms keep the origin where it was, they match the ones on `World`.
+impl<T: Clone> Grid<T> {
+    fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    fn remap(&self, size: IVec2, source: impl Fn(IVec2) -> IVec2) -> Self {
+        Grid::from_fn(size.x as usize, size.y as usize, |rel| {
+            self[self.origin + source(rel)].clone()
+        })
+        .with_origin(self.origin)
+    }
+
+    pub fn rotate(&self, rotation: Rotation) -> Self {
+        let size = match rotation {
+            Rotation::R180 => self.size(),
+            _ => IVec2::new(self.size().y, self.size().x),
+        };
+        self.remap(size, |rel| rotation.inverse().apply(rel, size))
+    }
+
+    /// Mirrors the coordinates along `axis`, so `Axis::X` swaps left and right.
+    pub fn flip(&self, axis: Axis) -> Self {
+        let size = self.size();
+        self.remap(size, |rel| match axis {
+            Axis::X => IVec2::new(size.x - 1 - rel.x, rel.y),
+            Axis::Y => IVec2::new(rel.x, size.y - 1 - rel.y),
+        })
+    }
+
+    pub fn transpose(&self) -> Self {
+        let size = IVec2::new(self.size().y, self.size().x);
+        self.remap(size, |rel| IVec2::new(rel.y, rel.x))
+    }
+
+    /// The part of the grid inside the inclusive rectangle from `min` to `max`, it keeps
+    /// its positions so the origin becomes the clamped `min`.
+    pub fn crop(&self, min: IVec2, max: IVec2) -> Self {
+        let min = min.max(self.origin);
+        let max = max.min(self.origin + self.size() - IVec2::ONE);
+        let size = (max - min + IVec2::ONE).max(IVec2::ZERO);
+        Grid::from_fn(size.x as usize, size.y as usize, |rel| {
+            self[min + rel].clone()
+        })
+        .with_origin(min)
+    }
+
+    /// Repeats the grid `across` times to the right and `down` times downwards. Each copy's
+    /// values come from `mapper`, which is given the tile index, `(0, 0)` being the original.
+    pub fn tile(&self, across: usize, down: usize, mapper: impl Fn(&T, IVec2) -> T) -> Self {
+        let size = self.size();
+        Grid::from_fn(self.width * across, self.height * down, |rel| {
+            let tile = IVec2::new(rel.x / size.x, rel.y / size.y);
+            mapper(&self[self.origin + rel - tile * size], tile)
+        })
+        .with_origin(self.origin)
+    }
+
+    /// Folds everything past `line` back over it, like folding paper. Cells on the line are
+    /// dropped and `merge` combines the cell that was there with the one folded onto it. The
+    /// origin only moves if the folded part is longer than the part it lands on.
+    pub fn fold(&self, axis: Axis, line: i32, merge: impl Fn(&T, &T) -> T) -> Self {
+        let index = axis.index();
+        let end = self.origin + self.size() - IVec2::ONE;
+        assert!(
+            (self.origin[index]..=end[index]).contains(&line),
+            "Fold line {line} is outside the grid"
+        );
+        let mut min = self.origin;
+        min[index] = min[index].min(2 * line - end[index]);
+        let mut size = self.size();
+        size[index] = line - min[index];
+        Grid::from_fn(size.x as usize, size.y as usize, |rel| {
+            let pos = min + rel;
+            let mut folded = pos;
+            folded[index] = 2 * line - pos[index];
+            match (self.get(pos), self.get(folded)) {
+                (Some(kept), Some(folded)) => merge(kept, folded),
+                (Some(cell), None) | (None, Some(cell)) => cell.clone(),
+                (None, None) => unreachable!("{pos} has nothing on either side of the fold"),
+            }
+        })
+        .with_origin(min)
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &T {
+        let i = self.index_of(pos);
+        &self.data[i.unwrap_or_else(|| panic!("{pos} is outside the grid"))]
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut T {
+        let i = self.index_of(pos);
+        &mut self.data[i.unwrap_or_else(|| panic!("{pos} is outside the grid"))]
+    }
+}
+
+impl<T> GridLike for Grid<T> {
+    type Cell = T;
+
+    fn get(&self, pos: IVec2) -> Option<&T> {
+        Grid::get(self, pos)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        Grid::iter(self)
+    }
+
+    fn in_bounds(&self, pos: IVec2) -> bool {
+        Grid::in_bounds(self, pos)
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        if self.data.is_empty() {
+            None
+        } else {
+            let size = IVec2::new(self.width as i32, self.height as i32);
+            Some((self.origin, self.origin + size - IVec2::ONE))
+        }
+    }
+}
+
+/// A set of positions, each cell holds its own position.
+impl GridLike for HashSet<IVec2> {
+    type Cell = IVec2;
+
+    fn get(&self, pos: IVec2) -> Option<&IVec2> {
+        HashSet::get(self, &pos)
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        let mut positions = HashSet::iter(self);
+        let first = *positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (min.min(*pos), max.max(*pos))
+        }))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &IVec2)> + '_ {
+        HashSet::iter(self).map(|pos| (*pos, pos))
+    }
+}
+
+impl<T> From<Grid<T>> for World<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let positions = grid.positions().collect_vec();
+        World {
+            world: positions.into_iter().zip(grid.data).collect(),
+        }
+    }
+}
+
+/// Missing cells become `T::default()`, use `Grid::from_world` to pick the fill.
+impl<T: Clone + Default> From<&World<T>> for Grid<T> {
+    fn from(world: &World<T>) -> Self {
+        Grid::from_world(world, T::default())
+    }
+}
+
+#[test]
+pub fn dense_grid() {
+    let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+    assert_eq!(grid[IVec2::new(2, 1)], 6);
+    assert_eq!(grid.get(IVec2::new(3, 0)), None);
+    assert!(!grid.in_bounds(IVec2::new(0, -1)));
+    assert_eq!(grid.rows().collect_vec(), vec![&[1, 2, 3][..], &[4, 5, 6]]);
+    assert_eq!(grid.column(1).copied().collect_vec(), vec![2, 5]);
+    assert_eq!(grid.columns().count(), 3);
+    assert!(std::panic::catch_unwind(|| grid.row(-1)).is_err());
+    assert_eq!(grid.bounds(), Some((IVec2::ZERO, IVec2::new(2, 1))));
+    assert!(Grid::from_rows(vec![vec![1], vec![2, 3]]).is_err());
+    assert_eq!(grid.neighbors4(IVec2::ZERO).len(), 2);
+    assert_eq!(grid.neighbors8(IVec2::new(1, 0)).len(), 5);
+
+    let mut world = World::from(grid.clone());
+    world.world.remove(&IVec2::new(0, 0));
+    world.world.insert(IVec2::new(-1, 1), 9);
+    let back = Grid::from(&world);
+    assert_eq!(back.origin(), IVec2::new(-1, 0));
+    assert_eq!(
+        back.rows().collect_vec(),
+        vec![&[0, 0, 2, 3][..], &[9, 4, 5, 6]]
+    );
+
+    // The same search runs over both representations.
+    let end = IVec2::new(2, 1);
+    let weight = |pos: IVec2, g: &mut Grid<i32>, _: &mut ()| g.get(pos).map(|v| *v as usize);
+    let cost = grid
+        .clone()
+        .min_cost_4(&mut (), IVec2::ZERO, |pos, _, _, _| pos == end, weight);
+    assert_eq!(cost, Some(2 + 3 + 6));
+    let mut world = World::from(grid);
+    let path = world.min_path_4(
+        &mut (),
+        IVec2::ZERO,
+        |pos, _, _| pos == end,
+        |pos, w, _| w.world.get(&pos).map(|v| *v as usize),
+    );
+    assert_eq!(path.map(|(cost, _)| cost), Some(11));
+}
+
+#[test]
+pub fn grid_like_storages() {
+    fn occupied<G: GridLike>(grid: &G) -> usize {
+        grid.iter().count()
+    }
+    let set: HashSet<IVec2> = [IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(3, -2)]
+        .into_iter()
+        .collect();
+    assert_eq!(occupied(&set), 3);
+    assert_eq!(set.bounds(), Some((IVec2::new(0, -2), IVec2::new(3, 0))));
+    assert_eq!(
+        set.neighbors8(IVec2::ZERO).into_vec(),
+        vec![IVec2::new(1, 0)]
+    );
+    let world = World {
+        world: set.iter().map(|pos| (*pos, '#')).collect(),
+    };
+    assert_eq!(occupied(&world), 3);
+    assert_eq!(
+        world.neighbors4(IVec2::new(1, 0)).into_vec(),
+        vec![IVec2::ZERO]
+    );
+    assert_eq!(occupied(&Grid::from(&world)), 12);
+}
+
+#[test]
+pub fn grid_transforms() {
+    let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]])
+        .unwrap()
+        .with_origin(IVec2::new(-1, 2));
+    let rows = |grid: &Grid<i32>| grid.rows().map(<[i32]>::to_vec).collect_vec();
+    assert_eq!(
+        rows(&grid.rotate(Rotation::R90)),
+        vec![vec![5, 3, 1], vec![6, 4, 2]]
+    );
+    assert_eq!(
+        rows(&grid.rotate(Rotation::R270)),
+        vec![vec![2, 4, 6], vec![1, 3, 5]]
+    );
+    assert_eq!(grid.rotate(Rotation::R180).rotate(Rotation::R180), grid);
+    assert_eq!(rows(&grid.flip(Axis::Y))[0], vec![5, 6]);
+    assert_eq!(grid.transpose().transpose(), grid);
+    assert_eq!(grid.rotate(Rotation::R90).origin(), IVec2::new(-1, 2));
+
+    let cropped = grid.crop(IVec2::new(0, 3), IVec2::new(9, 9));
+    assert_eq!(cropped.bounds(), Some((IVec2::new(0, 3), IVec2::new(0, 4))));
+    assert_eq!(rows(&cropped), vec![vec![4], vec![6]]);
+
+    let tiled = grid.tile(2, 2, |val, tile| val + 10 * (tile.x + tile.y));
+    assert_eq!(tiled.width(), 4);
+    assert_eq!(rows(&tiled)[2], vec![5, 6, 15, 16]);
+    assert_eq!(rows(&tiled)[5], vec![15, 16, 25, 26]);
+    assert_eq!(tiled[IVec2::new(2, 7)], 26);
+
+    let folded = grid.fold(Axis::Y, 3, |kept, folded| kept * 10 + folded);
+    assert_eq!(folded.origin(), IVec2::new(-1, 2));
+    assert_eq!(rows(&folded), vec![vec![15, 26]]);
+    let folded = grid.fold(Axis::X, -1, |kept, folded| kept + folded);
+    assert_eq!(folded.origin(), IVec2::new(-2, 2));
+    assert_eq!(rows(&folded), vec![vec![2], vec![4], vec![6]]);
+}
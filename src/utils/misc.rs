@@ -1,3 +1,4 @@
+use super::GridLike;
 pub use glam::{IVec2, IVec3, Quat, Vec2, Vec3};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
@@ -32,27 +33,6 @@ pub fn from_digits(digits: &[i64]) -> i64 {
     acc
 }
 
-#[derive(Clone)]
-pub struct VecWorld<T: Default> {
-    data: Vec<Vec<T>>,
-}
-
-impl<T: Default + Clone> VecWorld<T> {
-    pub fn new() -> Self {
-        VecWorld {
-            data: vec![vec![T::default(); 50000]; 50000],
-        }
-    }
-
-    pub fn get(&self, pos: IVec2) -> &T {
-        &self.data[(pos.x + 25000) as usize][(pos.y + 25000) as usize]
-    }
-
-    pub fn set(&mut self, pos: IVec2, val: T) {
-        self.data[(pos.x + 25000) as usize][(pos.y + 25000) as usize] = val;
-    }
-}
-
 pub fn get_neighbors(pos: IVec2) -> [IVec2; 8] {
     let x = pos.x;
     let y = pos.y;
@@ -96,21 +76,15 @@ pub fn get_cardinal_neighbors(pos: IVec2) -> [IVec2; 4] {
 }
 
 pub fn pretty_print_set(set: &HashSet<IVec2>, str_fn: &impl Fn(&IVec2) -> String, width: usize) {
-    let max_y = set.iter().map(|pos| pos.y).max().unwrap();
-    let max_x = set.iter().map(|pos| pos.x).max().unwrap();
-    let min_x = set.iter().map(|pos| pos.x).min().unwrap();
-    let min_y = set.iter().map(|pos| pos.y).min().unwrap();
-    for y in min_y..=max_y {
-        let mut row = vec![];
-        for x in min_x..=max_x {
-            let pos = IVec2::new(x, y);
-            if let Some(val) = set.get(&pos) {
-                row.push(str_fn(val).pad_to_width(width));
-            } else {
-                row.push(" ".to_string().pad_to_width(width));
-            }
-        }
-        println!("{}", row.iter().join(""));
+    let (min, max) = set.bounds().unwrap();
+    for y in min.y..=max.y {
+        let row = (min.x..=max.x)
+            .map(|x| match GridLike::get(set, IVec2::new(x, y)) {
+                Some(val) => str_fn(val).pad_to_width(width),
+                None => " ".pad_to_width(width),
+            })
+            .join("");
+        println!("{}", row);
     }
 }
 
@@ -1,9 +1,24 @@
 pub mod aocapi;
 pub mod aocdata;
+mod automaton;
+mod chunked;
+pub mod cli;
+mod grid;
 mod misc;
+mod ocr;
+pub mod progress;
 pub mod puzzle;
 pub mod puzzleanswer;
+mod render;
+pub mod runner;
+mod sight;
+pub use automaton::*;
+pub use chunked::*;
+pub use grid::*;
 pub use misc::*;
+pub use ocr::*;
 pub use puzzleanswer::*;
+pub use render::*;
+pub use sight::*;
 mod world;
 pub use world::*;
@@ -0,0 +1,217 @@
+use super::World;
+use anyhow::{anyhow, Result};
+use glam::IVec2;
+use hashbrown::HashSet;
+use itertools::Itertools;
+
+/// The 4x6 letters most puzzles draw, glyphs are separated by a blank column.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 6x10 letters used by the star message puzzles, glyphs are separated by two blank columns.
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+fn glyph_string(pixels: &HashSet<IVec2>, min: IVec2, max: IVec2) -> String {
+    (min.y..=max.y)
+        .map(|y| {
+            (min.x..=max.x)
+                .map(|x| {
+                    if pixels.contains(&IVec2::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// Reads the letters drawn by the lit pixels, with y increasing downwards like the puzzle text.
+pub fn ocr_set(pixels: &HashSet<IVec2>) -> Result<String> {
+    let min_y = pixels
+        .iter()
+        .map(|pos| pos.y)
+        .min()
+        .ok_or(anyhow!("Nothing to read"))?;
+    let max_y = pixels.iter().map(|pos| pos.y).max().unwrap();
+    let font: &[(char, &str)] = match max_y - min_y + 1 {
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        height => return Err(anyhow!("No font is {height} pixels tall")),
+    };
+
+    let columns: HashSet<i32> = pixels.iter().map(|pos| pos.x).collect();
+    let mut glyphs = vec![];
+    for x in columns.iter().copied().sorted() {
+        match glyphs.last_mut() {
+            Some((_, end)) if *end == x - 1 => *end = x,
+            _ => glyphs.push((x, x)),
+        }
+    }
+
+    // Letters are usually separated by blank columns, but wide ones like Y can touch their
+    // neighbour so each run of columns is matched greedily, widest glyphs first.
+    let font = font
+        .iter()
+        .map(|(letter, pattern)| {
+            (
+                *letter,
+                *pattern,
+                pattern.lines().next().unwrap().len() as i32,
+            )
+        })
+        .sorted_by_key(|(_, _, width)| -width)
+        .collect_vec();
+    let mut text = String::new();
+    for (start, end) in glyphs {
+        let mut x = start;
+        while x <= end {
+            let (letter, width) = font
+                .iter()
+                .filter(|(_, _, width)| x + width - 1 <= end)
+                .find(|(_, pattern, width)| {
+                    glyph_string(
+                        pixels,
+                        IVec2::new(x, min_y),
+                        IVec2::new(x + width - 1, max_y),
+                    ) == *pattern
+                })
+                .map(|(letter, _, width)| (*letter, *width))
+                .ok_or_else(|| {
+                    let glyph = glyph_string(pixels, IVec2::new(x, min_y), IVec2::new(end, max_y));
+                    anyhow!("Unknown glyph at x = {x}:\n{glyph}")
+                })?;
+            text.push(letter);
+            x += width;
+        }
+    }
+    Ok(text)
+}
+
+impl<T> World<T> {
+    /// Reads the letters drawn by the cells that `is_lit` returns true for.
+    pub fn ocr(&self, is_lit: impl Fn(&T) -> bool) -> Result<String> {
+        let pixels: HashSet<IVec2> = self
+            .world
+            .iter()
+            .filter(|(_, val)| is_lit(val))
+            .map(|(pos, _)| *pos)
+            .collect();
+        ocr_set(&pixels)
+    }
+}
+
+#[cfg(test)]
+fn render(font: &[(char, &str)], text: &str, gap: i32) -> HashSet<IVec2> {
+    let mut pixels = HashSet::new();
+    let mut offset = 0;
+    for letter in text.chars() {
+        let (_, glyph) = font.iter().find(|(c, _)| *c == letter).unwrap();
+        for (y, row) in glyph.lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    pixels.insert(IVec2::new(offset + x as i32, y as i32));
+                }
+            }
+        }
+        offset += glyph.lines().next().unwrap().len() as i32 + gap;
+    }
+    pixels
+}
+
+#[test]
+pub fn read_letters() {
+    let small = render(&SMALL_FONT, "HIJKLY", 1);
+    assert_eq!(ocr_set(&small).unwrap(), "HIJKLY");
+    assert_eq!(ocr_set(&render(&SMALL_FONT, "YZ", 0)).unwrap(), "YZ");
+    assert_eq!(ocr_set(&render(&LARGE_FONT, "XZJ", 2)).unwrap(), "XZJ");
+
+    let world = World {
+        world: small.iter().map(|pos| (*pos, '#')).collect(),
+    };
+    assert_eq!(world.ocr(|c| *c == '#').unwrap(), "HIJKLY");
+
+    let mut unknown = small;
+    unknown.insert(IVec2::new(1, 0));
+    assert!(ocr_set(&unknown).is_err());
+}
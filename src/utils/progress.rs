@@ -0,0 +1,131 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+const REDRAW_MILLIS: u64 = 100;
+
+/// Counts iterations of a long running loop and keeps a status line with the rate and ETA up to
+/// date on stderr. It only needs `&self` so the same counter can be shared by rayon threads.
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    count: AtomicU64,
+    start: Instant,
+    last_draw: AtomicU64,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(label: impl ToString, total: Option<u64>) -> Self {
+        Self {
+            label: label.to_string(),
+            total,
+            count: AtomicU64::new(0),
+            start: Instant::now(),
+            last_draw: AtomicU64::new(0),
+            enabled: true,
+        }
+    }
+
+    /// Neither counts nor draws, so a hot loop pays nothing for it. Used for benchmarks and tests.
+    pub fn hidden(label: impl ToString, total: Option<u64>) -> Self {
+        let mut progress = Self::new(label, total);
+        progress.enabled = false;
+        progress
+    }
+
+    pub fn inc(&self, n: u64) {
+        if !self.enabled {
+            return;
+        }
+        let count = self.count.fetch_add(n, Ordering::Relaxed) + n;
+        let now = self.start.elapsed().as_millis() as u64;
+        let last = self.last_draw.load(Ordering::Relaxed);
+        // Only the thread that wins the exchange redraws, the rest carry on counting.
+        if now >= last + REDRAW_MILLIS
+            && self
+                .last_draw
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            eprint!("\r\x1b[2K{}", self.status_for(count));
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Iterations per second since the progress was created.
+    pub fn rate(&self) -> f64 {
+        self.count() as f64 / self.start.elapsed().as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total?.saturating_sub(self.count());
+        let rate = self.rate();
+        if rate > 0. {
+            Some(Duration::from_secs_f64(remaining as f64 / rate))
+        } else {
+            None
+        }
+    }
+
+    pub fn status(&self) -> String {
+        self.status_for(self.count())
+    }
+
+    fn status_for(&self, count: u64) -> String {
+        let mut status = format!("{}: {count}", self.label);
+        if let Some(total) = self.total {
+            let percent = count as f64 * 100. / total.max(1) as f64;
+            status.push_str(&format!("/{total} ({percent:.1}%)"));
+        }
+        status.push_str(&format!(", {:.0}/s", self.rate()));
+        if let Some(eta) = self.eta() {
+            status.push_str(&format!(", ETA {}", format_duration(eta)));
+        }
+        status
+    }
+}
+
+/// Clears the status line and leaves a summary in its place.
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.enabled {
+            eprintln!(
+                "\r\x1b[2K{}: {} in {}",
+                self.label,
+                self.count(),
+                format_duration(self.start.elapsed())
+            );
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{:.1}s", duration.as_secs_f64()),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[test]
+pub fn progress_counts_across_threads() {
+    use rayon::prelude::*;
+
+    let progress = Progress::new("Items", Some(2000));
+    (0..1000).into_par_iter().for_each(|_| progress.inc(1));
+    assert_eq!(progress.count(), 1000);
+    assert!(progress.rate() > 0.);
+    assert!(progress.eta().is_some());
+    assert!(progress.status().starts_with("Items: 1000/2000 (50.0%)"));
+    assert_eq!(format_duration(Duration::from_secs(125)), "2m 5s");
+    let hidden = Progress::hidden("Items", None);
+    hidden.inc(5);
+    assert_eq!(hidden.count(), 0);
+    assert_eq!(hidden.eta(), None);
+}
@@ -2,13 +2,24 @@ use core::fmt::Debug;
 use std::{
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
-    fmt::Display,
+    fmt::{Arguments, Display},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
 use super::{
     aocapi::{get_input, read_session_cookie},
-    aocdata::{AnswerState, Part, PuzzleState, TestCase},
-    PuzzleAnswer,
+    aocdata::{AnswerState, Part, PuzzleState, SubmitMode, SubmitOptions, TestCase},
+    progress::Progress,
+    runner::{
+        catch_panic, CancellationToken, RunOutcome, RunResult, TestOutcome, TestReport, TestResult,
+    },
+    Answer, PuzzleAnswer,
 };
 use anyhow::{anyhow, Context, Error, Result};
 
@@ -73,17 +84,125 @@ impl Display for PuzzleDay {
     }
 }
 
+/// How much a solver prints besides its answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Info,
+    Debug,
+}
+
+static DEFAULT_VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Quiet as u8);
+
+impl Verbosity {
+    /// Sets the verbosity new contexts start with, the runner picks one per mode.
+    pub fn set_default(self) {
+        DEFAULT_VERBOSITY.store(self as u8, Ordering::Relaxed);
+    }
+}
+
+/// Quiet unless the runner asked for more, so tests and batch runs aren't flooded.
+impl Default for Verbosity {
+    fn default() -> Self {
+        match DEFAULT_VERBOSITY.load(Ordering::Relaxed) {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Info,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+/// Everything a solver gets besides its input. Test cases can set parameters, e.g. a smaller step
+/// count for an example, while the real run leaves them empty so the solver uses its defaults.
+#[derive(Debug, Clone, Default)]
+pub struct PuzzleContext {
+    pub params: BTreeMap<String, String>,
+    pub verbosity: Verbosity,
+    /// Set by the runner for single runs on the real input, never for tests or benchmarks.
+    pub show_progress: bool,
+    pub cancel: CancellationToken,
+}
+
+impl PuzzleContext {
+    pub fn with_params(params: BTreeMap<String, String>) -> Self {
+        Self {
+            params,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// For output that is expensive to build, e.g. printing a whole map.
+    pub fn enabled(&self, level: Verbosity) -> bool {
+        self.verbosity >= level
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// For solvers to bail out with `?` once the runner has given up on them.
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(anyhow!("Cancelled"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// A progress counter that only draws its status line when the runner wants it to.
+    pub fn progress(&self, label: impl ToString, total: Option<u64>) -> Progress {
+        if self.show_progress && self.enabled(Verbosity::Info) {
+            Progress::new(label, total)
+        } else {
+            Progress::hidden(label, total)
+        }
+    }
+
+    /// Takes `format_args!` so nothing is formatted unless the message is shown.
+    pub fn info(&self, msg: Arguments) {
+        if self.enabled(Verbosity::Info) {
+            eprintln!("{msg}");
+        }
+    }
+
+    pub fn debug(&self, msg: Arguments) {
+        if self.enabled(Verbosity::Debug) {
+            eprintln!("{msg}");
+        }
+    }
+
+    pub fn param<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        match self.params.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|err| anyhow!("Invalid value {value:?} for parameter {key}: {err}")),
+            None => Ok(default),
+        }
+    }
+}
+
+pub type SolverFn = dyn Fn(&str, &PuzzleContext) -> Result<PuzzleAnswer> + Sync;
+
 #[derive(Clone)]
 pub struct PuzzleInfo {
     pub id: (PuzzleYear, PuzzleDay),
-    pub solver: &'static dyn Fn(&str) -> Result<PuzzleAnswer>,
+    pub solver: &'static SolverFn,
     pub tests: Vec<TestCase>,
 }
 
 pub trait SolvePuzzle {
     type Output: Into<PuzzleAnswer>;
     fn puzzle_year_day() -> (i32, u32);
-    fn solve(input: &str) -> Result<Self::Output>;
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output>;
     fn test_cases() -> Vec<TestCase> {
         Vec::new()
     }
@@ -93,6 +212,7 @@ pub trait PuzzleFns {
     fn run_tests() -> Result<()>;
     fn puzzle_info() -> PuzzleInfo;
     fn try_submit() -> Result<(bool, bool)>;
+    fn try_submit_with(options: &SubmitOptions) -> Result<(bool, bool)>;
 }
 
 impl<T> PuzzleFns for T
@@ -117,61 +237,281 @@ where
     {
         Self::puzzle_info().try_submit()
     }
+    fn try_submit_with(options: &SubmitOptions) -> Result<(bool, bool)>
+    where
+        Self: Sized + 'static,
+    {
+        Self::puzzle_info().try_submit_with(options)
+    }
 }
 
-fn run_solve<T: SolvePuzzle + 'static>(input: &str) -> Result<PuzzleAnswer> {
-    let res = T::solve(input)?;
+/// Turns each example of a puzzle into its own test, e.g. `y2023_d5_part_a_example_0`, so they
+/// run offline and in parallel. build.rs counts the examples each part lists in `test_cases`
+/// and an extra `..._examples_covered` test fails if that count is wrong, e.g. for cases built
+/// in a loop.
+macro_rules! puzzle_tests {
+    (@cases $puzzle:ty, $year:literal, $day:literal, a: [$($a:literal),*], b: [$($b:literal),*]) => {
+        paste::paste! {
+            $(
+                #[test]
+                fn [<y $year _d $day _part_a_example_ $a>]() -> anyhow::Result<()> {
+                    <$puzzle as $crate::utils::puzzle::PuzzleFns>::puzzle_info()
+                        .run_test_case($crate::utils::aocdata::Part::A, $a)
+                }
+            )*
+            $(
+                #[test]
+                fn [<y $year _d $day _part_b_example_ $b>]() -> anyhow::Result<()> {
+                    <$puzzle as $crate::utils::puzzle::PuzzleFns>::puzzle_info()
+                        .run_test_case($crate::utils::aocdata::Part::B, $b)
+                }
+            )*
+            #[test]
+            fn [<y $year _d $day _examples_covered>]() {
+                let info = <$puzzle as $crate::utils::puzzle::PuzzleFns>::puzzle_info();
+                let count = |part| info.tests.iter().filter(|test| test.part == part).count();
+                assert_eq!(count($crate::utils::aocdata::Part::A), <[usize]>::len(&[$($a),*]));
+                assert_eq!(count($crate::utils::aocdata::Part::B), <[usize]>::len(&[$($b),*]));
+            }
+        }
+    };
+    ($puzzle:ty, $year:literal, $day:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/puzzle_tests/", file!()));
+    };
+}
+pub(crate) use puzzle_tests;
+
+fn run_solve<T: SolvePuzzle + 'static>(input: &str, ctx: &PuzzleContext) -> Result<PuzzleAnswer> {
+    let res = T::solve(input, ctx)?;
     Ok(res.into())
 }
 
 impl PuzzleInfo {
     pub fn new<T: SolvePuzzle + 'static>() -> PuzzleInfo {
+        Self::try_new::<T>().unwrap()
+    }
+
+    pub fn try_new<T: SolvePuzzle + 'static>() -> Result<PuzzleInfo> {
         let (raw_year, raw_day) = T::puzzle_year_day();
-        let year = PuzzleYear::try_from(raw_year)
-            .context("Year should be valid")
-            .unwrap();
-        let day = PuzzleDay::try_from(raw_day)
-            .context("Day should be valid")
-            .unwrap();
-
-        PuzzleInfo {
+        let year = PuzzleYear::try_from(raw_year).context("Year should be valid")?;
+        let day = PuzzleDay::try_from(raw_day).context("Day should be valid")?;
+        let tests = catch_panic(T::test_cases)
+            .map_err(|report| anyhow!("Building test cases for {year} day {day} {report}"))?;
+
+        Ok(PuzzleInfo {
             id: (year, day),
-            solver: (&run_solve::<T>) as &'static dyn Fn(&str) -> Result<PuzzleAnswer>,
-            tests: T::test_cases(),
+            solver: &run_solve::<T> as &'static SolverFn,
+            tests,
+        })
+    }
+
+    fn run_test(&self, test: &TestCase) -> Result<TestOutcome> {
+        let ctx = PuzzleContext::with_params(test.params.clone());
+        let result = (self.solver)(&test.input.get_value()?, &ctx)?;
+        let answer = match test.part {
+            Part::A => result.0,
+            Part::B => result.1,
+        };
+        let actual = answer.ok_or(anyhow!("Expected the solution to return an answer."))?;
+        let expected: Answer = test.expected.get_value()?.parse()?;
+        if actual == expected {
+            Ok(TestOutcome::Passed)
+        } else {
+            Ok(TestOutcome::Failed {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            })
+        }
+    }
+
+    fn run_indexed_test(&self, index: usize, test: &TestCase) -> TestResult {
+        let start = Instant::now();
+        let outcome = match catch_panic(|| self.run_test(test)) {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(err)) => TestOutcome::Error(format!("{err:#}")),
+            Err(report) => TestOutcome::Error(report.to_string()),
+        };
+        TestResult {
+            index,
+            part: test.part,
+            outcome,
+            duration: start.elapsed(),
+        }
+    }
+
+    /// Runs every test case, recording failures instead of stopping at the first one.
+    pub fn test_report(&self) -> TestReport {
+        let mut part_counts = [0, 0];
+        let results = self
+            .tests
+            .iter()
+            .map(|test| {
+                let count = &mut part_counts[test.part as usize];
+                *count += 1;
+                self.run_indexed_test(*count - 1, test)
+            })
+            .collect();
+        TestReport {
+            id: self.id,
+            results,
+        }
+    }
+
+    /// Runs the index-th test case for a part, numbering the examples of each part from 0.
+    pub fn run_test_case(&self, part: Part, index: usize) -> Result<()> {
+        let test = self
+            .tests
+            .iter()
+            .filter(|test| test.part == part)
+            .nth(index)
+            .ok_or_else(|| anyhow!("No part {part:?} example {index}"))?;
+        let result = self.run_indexed_test(index, test);
+        match result.outcome {
+            TestOutcome::Passed => Ok(()),
+            _ => Err(anyhow!("\n{result}")),
         }
     }
 
     pub fn run_tests(&self) -> Result<()> {
-        for test in &self.tests {
-            let result = (self.solver)(&test.input.get_value()?)?;
-            let answer = match test.part {
-                Part::A => result.0,
-                Part::B => result.1,
-            };
-            let answer = answer.ok_or(anyhow!("Expected the solution to return an answer."))?;
-            let expected = test.expected.get_value()?;
-            #[cfg(test)]
-            {
-                assert_eq!(answer, expected);
+        let report = self.test_report();
+        print!("{report}");
+        if report.passed() {
+            Ok(())
+        } else {
+            let (year, day) = self.id;
+            Err(anyhow!(
+                "{} of {} tests failed for {year} day {day}",
+                report.failures(),
+                report.results.len()
+            ))
+        }
+    }
+
+    /// Solves the real input `iterations` times, fetching it first if it isn't cached.
+    /// Stops at the first run that fails or takes longer than `timeout`. Benchmarks pass
+    /// `show_progress: false` so status lines don't end up in the timings.
+    pub fn run(
+        &self,
+        cookie: &str,
+        iterations: usize,
+        timeout: Option<Duration>,
+        show_progress: bool,
+    ) -> RunResult {
+        let (year, day) = self.id;
+        let mut durations = Vec::new();
+        let outcome = match get_input(cookie, year, day) {
+            Ok(input) => {
+                let mut outcome = RunOutcome::Error("Not run".to_owned());
+                let ctx = PuzzleContext {
+                    show_progress,
+                    ..Default::default()
+                };
+                for _ in 0..iterations {
+                    let start = Instant::now();
+                    outcome = self.solve_with_timeout(&input, &ctx, timeout);
+                    durations.push(start.elapsed());
+                    if !matches!(outcome, RunOutcome::Solved(_)) {
+                        break;
+                    }
+                }
+                outcome
             }
-            if answer != expected {
-                return Err(anyhow!("Expected: {answer} to equal: {expected}"));
+            Err(err) => RunOutcome::Error(format!("{err:#}")),
+        };
+        RunResult {
+            id: self.id,
+            outcome,
+            durations,
+        }
+    }
+
+    /// Runs the solver on its own thread so the runner can stop waiting for it. The thread is
+    /// abandoned on timeout after cancelling `ctx`, it only stops if the solver checks.
+    fn solve_with_timeout(
+        &self,
+        input: &str,
+        ctx: &PuzzleContext,
+        timeout: Option<Duration>,
+    ) -> RunOutcome {
+        let to_outcome = |res| match res {
+            Ok(Ok(answer)) => RunOutcome::Solved(answer),
+            Ok(Err(err)) => RunOutcome::Error(format!("{err:#}")),
+            Err(report) => RunOutcome::Panicked(report),
+        };
+        let solver = self.solver;
+        let timeout = match timeout {
+            Some(timeout) => timeout,
+            None => return to_outcome(catch_panic(|| solver(input, ctx))),
+        };
+        let (tx, rx) = channel();
+        let input = input.to_owned();
+        let thread_ctx = ctx.clone();
+        thread::spawn(move || tx.send(catch_panic(|| solver(&input, &thread_ctx))));
+        match rx.recv_timeout(timeout) {
+            Ok(res) => to_outcome(res),
+            Err(RecvTimeoutError::Timeout) => {
+                ctx.cancel.cancel();
+                RunOutcome::TimedOut(timeout)
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                RunOutcome::Error("The solver thread exited without an answer".to_owned())
             }
         }
-        Ok(())
     }
 
     pub fn try_submit(&self) -> Result<(bool, bool)> {
+        self.try_submit_with(&SubmitOptions::default())
+    }
+
+    pub fn try_submit_with(&self, options: &SubmitOptions) -> Result<(bool, bool)> {
         let cookie = read_session_cookie()?;
         let (year, day) = self.id;
         println!("Submitting year {} day {}", year, day);
         let input = get_input(&cookie, year, day)?;
-        let answer = (self.solver)(&input)?;
-        let mut state = PuzzleState::load(year, day);
-        let result = state.try_submit(&cookie, year, day, &answer)?;
-        state.save(year, day);
-        Ok(result)
+        let ctx = PuzzleContext {
+            show_progress: true,
+            ..Default::default()
+        };
+        let answer = (self.solver)(&input, &ctx)?;
+        submit_with_state(&cookie, year, day, &answer, options)
+    }
+}
+
+/// Submits an answer that was found without a registered solver, e.g. by hand.
+pub fn submit_manual(
+    year: PuzzleYear,
+    day: PuzzleDay,
+    part: Part,
+    answer: &str,
+    options: &SubmitOptions,
+) -> Result<bool> {
+    let cookie = read_session_cookie()?;
+    println!("Submitting year {} day {} part {:?}", year, day, part);
+    let answer: Answer = answer.parse()?;
+    let answer = match part {
+        Part::A => PuzzleAnswer(Some(answer), None),
+        Part::B => PuzzleAnswer(None, Some(answer)),
+    };
+    let (a, b) = submit_with_state(&cookie, year, day, &answer, options)?;
+    Ok(match part {
+        Part::A => a,
+        Part::B => b,
+    })
+}
+
+fn submit_with_state(
+    cookie: &str,
+    year: PuzzleYear,
+    day: PuzzleDay,
+    answer: &PuzzleAnswer,
+    options: &SubmitOptions,
+) -> Result<(bool, bool)> {
+    let mut state = PuzzleState::load(year, day);
+    let result = state.try_submit(cookie, year, day, answer, options)?;
+    if options.mode != SubmitMode::DryRun {
+        state.save(year, day)?;
     }
+    Ok(result)
 }
 
 #[derive(Clone)]
@@ -186,8 +526,15 @@ impl AoC {
         }
     }
 
+    /// Puzzles that can't be registered are reported and skipped so the rest still run.
     pub fn register<T: SolvePuzzle + 'static>(mut self) -> Self {
-        let info = PuzzleInfo::new::<T>();
+        let info = match PuzzleInfo::try_new::<T>() {
+            Ok(info) => info,
+            Err(err) => {
+                eprintln!("Unable to register {}: {err:#}", std::any::type_name::<T>());
+                return self;
+            }
+        };
         if let Some(puzzle) = self.puzzles.insert(info.id.clone(), info) {
             eprintln!(
                 "There was already a puzzle registered for {} day {}",
@@ -202,12 +549,47 @@ impl AoC {
         dbg!(ids);
     }
 
-    pub fn test_puzzle(&self, year: PuzzleYear, day: PuzzleDay) -> Result<()> {
-        let puzzle = self
-            .puzzles
+    /// Solves every registered puzzle, inputs that are already cached don't need a session cookie.
+    pub fn run_all(
+        &self,
+        iterations: usize,
+        timeout: Option<Duration>,
+        show_progress: bool,
+    ) -> impl Iterator<Item = RunResult> + '_ {
+        let cookie = read_session_cookie().unwrap_or_default();
+        self.puzzles
+            .values()
+            .map(move |puzzle| puzzle.run(&cookie, iterations, timeout, show_progress))
+    }
+
+    pub fn test_all(&self) -> Vec<TestReport> {
+        self.puzzles
+            .values()
+            .map(|puzzle| puzzle.test_report())
+            .collect()
+    }
+
+    pub fn get(&self, year: PuzzleYear, day: PuzzleDay) -> Result<&PuzzleInfo> {
+        self.puzzles
             .get(&(year, day))
-            .ok_or(anyhow!("No puzzle for {year} day {day}"))?;
-        puzzle.run_tests()
+            .ok_or(anyhow!("No puzzle for {year} day {day}"))
+    }
+
+    pub fn run_puzzle(
+        &self,
+        year: PuzzleYear,
+        day: PuzzleDay,
+        iterations: usize,
+        timeout: Option<Duration>,
+        show_progress: bool,
+    ) -> Result<RunResult> {
+        let puzzle = self.get(year, day)?;
+        let cookie = read_session_cookie().unwrap_or_default();
+        Ok(puzzle.run(&cookie, iterations, timeout, show_progress))
+    }
+
+    pub fn test_puzzle(&self, year: PuzzleYear, day: PuzzleDay) -> Result<()> {
+        self.get(year, day)?.run_tests()
     }
 }
 
@@ -219,7 +601,7 @@ impl SolvePuzzle for AoC2022Day1PartA {
         (2022, 1)
     }
 
-    fn solve(_input: &str) -> Result<Self::Output> {
+    fn solve(_input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         Ok("1")
     }
 
@@ -240,8 +622,8 @@ impl SolvePuzzle for AoC2022Day1PartB {
         (2022, 1)
     }
 
-    fn solve(_input: &str) -> Result<Self::Output> {
-        Ok(PuzzleAnswer(Some("1".to_owned()), None))
+    fn solve(_input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
+        Ok(PuzzleAnswer(Some("1".into()), None))
     }
 }
 
@@ -252,7 +634,7 @@ impl SolvePuzzle for AoC2022Day2 {
         (2022, 2)
     }
 
-    fn solve(_input: &str) -> Result<Self::Output> {
+    fn solve(_input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         Ok((Some("1".to_owned()), "2".to_owned()))
     }
 }
@@ -264,11 +646,151 @@ impl SolvePuzzle for AoC2022Day3 {
         (2022, 3)
     }
 
-    fn solve(_input: &str) -> Result<Self::Output> {
+    fn solve(_input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         Ok((Some("1".to_owned()), "2".to_owned()))
     }
 }
 
+pub struct AoC2022Day4;
+impl SolvePuzzle for AoC2022Day4 {
+    type Output = (usize, String);
+    fn puzzle_year_day() -> (i32, u32) {
+        (2022, 4)
+    }
+
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
+        if input.is_empty() {
+            return Err(anyhow!("No input"));
+        }
+        Ok((input.len(), input.replace('.', "#")))
+    }
+
+    fn test_cases() -> Vec<TestCase> {
+        vec![
+            TestCase::new(Part::A, "abc", 3),
+            TestCase::new(Part::A, "abc", 4),
+            TestCase::new(Part::B, "", "#"),
+            TestCase::new(Part::B, "..\n.a", "##\n#a"),
+        ]
+    }
+}
+
+pub struct AoC2022Day5;
+impl SolvePuzzle for AoC2022Day5 {
+    type Output = usize;
+    fn puzzle_year_day() -> (i32, u32) {
+        (2022, 5)
+    }
+
+    fn solve(_input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
+        loop {
+            ctx.check_cancelled()?;
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+#[test]
+pub fn timeout_cancels_solver() {
+    let info = AoC2022Day5::puzzle_info();
+    let ctx = PuzzleContext::default();
+    let timeout = Some(Duration::from_millis(20));
+    assert_eq!(
+        info.solve_with_timeout("", &ctx, timeout),
+        RunOutcome::TimedOut(Duration::from_millis(20))
+    );
+    assert!(ctx.is_cancelled());
+    assert!(ctx.check_cancelled().is_err());
+
+    let info = AoC2022Day4::puzzle_info();
+    assert_eq!(
+        info.solve_with_timeout("ab", &PuzzleContext::default(), timeout),
+        RunOutcome::Solved((2, "ab").into())
+    );
+}
+
+pub struct AoC2022Day6;
+impl SolvePuzzle for AoC2022Day6 {
+    type Output = usize;
+    fn puzzle_year_day() -> (i32, u32) {
+        (2022, 6)
+    }
+
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
+        Ok(input.parse().unwrap())
+    }
+
+    fn test_cases() -> Vec<TestCase> {
+        vec![TestCase::new(Part::A, "x", 1)]
+    }
+}
+
+pub struct AoC2022Day26;
+impl SolvePuzzle for AoC2022Day26 {
+    type Output = usize;
+    fn puzzle_year_day() -> (i32, u32) {
+        (2022, 26)
+    }
+
+    fn solve(_input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
+        Ok(0)
+    }
+}
+
+#[test]
+pub fn panics_are_isolated() {
+    let info = AoC2022Day6::puzzle_info();
+    let timeout = Some(Duration::from_secs(10));
+    for timeout in [None, timeout] {
+        match info.solve_with_timeout("x", &PuzzleContext::default(), timeout) {
+            RunOutcome::Panicked(report) => {
+                assert!(report.message.contains("InvalidDigit"));
+                assert!(report.location.unwrap().starts_with("src/utils/puzzle.rs:"));
+            }
+            outcome => panic!("Expected a panic, got {outcome:?}"),
+        }
+    }
+    assert!(matches!(
+        &info.test_report().results[0].outcome,
+        TestOutcome::Error(err) if err.starts_with("panicked at src/utils/puzzle.rs:")
+    ));
+
+    let aoc = AoC::new()
+        .register::<AoC2022Day26>()
+        .register::<AoC2022Day6>();
+    assert_eq!(aoc.puzzles.len(), 1);
+}
+
+#[test]
+pub fn report_collects_failures() {
+    let report = AoC2022Day4::puzzle_info().test_report();
+    let outcomes: Vec<_> = report.results.iter().map(|x| x.outcome.clone()).collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            TestOutcome::Passed,
+            TestOutcome::Failed {
+                expected: "4".to_owned(),
+                actual: "3".to_owned()
+            },
+            TestOutcome::Error("No input".to_owned()),
+            TestOutcome::Passed,
+        ]
+    );
+    assert_eq!(report.failures(), 2);
+    assert!(AoC2022Day4::run_tests().is_err());
+}
+
+#[test]
+pub fn context_verbosity() {
+    let ctx = PuzzleContext::default();
+    assert_eq!(ctx.verbosity, Verbosity::Quiet);
+    assert!(!ctx.enabled(Verbosity::Info));
+    let ctx = ctx.with_verbosity(Verbosity::Info);
+    assert!(ctx.enabled(Verbosity::Info));
+    assert!(!ctx.enabled(Verbosity::Debug));
+}
+
 #[test]
 pub fn basic_test() {
     let aoc = AoC::new()
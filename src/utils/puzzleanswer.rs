@@ -1,47 +1,342 @@
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct PuzzleAnswer(pub Option<String>, pub Option<String>);
+use std::{convert::TryFrom, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use itertools::Itertools;
+use num::BigInt;
+
+/// An answer that remembers what kind of value it is, equality ignores formatting
+/// such as trailing whitespace so `Integer(12)` equals `Text("12")`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    MultiLine(String),
+}
+
+impl Answer {
+    /// Trims trailing whitespace from every line and drops blank lines around the answer.
+    pub fn text(text: &str) -> Answer {
+        let lines = text.lines().map(str::trim_end).collect_vec();
+        let start = lines.iter().position(|line| !line.is_empty());
+        let end = lines.iter().rposition(|line| !line.is_empty());
+        match (start, end) {
+            (Some(start), Some(end)) if start < end => {
+                Answer::MultiLine(lines[start..=end].join("\n"))
+            }
+            (Some(start), _) => Answer::Text(lines[start].trim().to_owned()),
+            _ => Answer::Text(String::new()),
+        }
+    }
+
+    /// Like parsing, but text that can't be a number stays text instead of being an error.
+    pub fn classify(text: &str) -> Answer {
+        text.parse().unwrap_or_else(|_| Answer::text(text))
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(num) => Some(*num),
+            _ => None,
+        }
+    }
+}
+
+/// Reads text that may be a number, like an expected value or a hand typed answer.
+/// Numbers with leading zeros stay text since the zeros are probably significant.
+impl FromStr for Answer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let answer = Answer::text(s);
+        let text = match &answer {
+            Answer::Text(text) => text,
+            _ => return Ok(answer),
+        };
+        let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+        if digits.is_empty()
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || (digits.starts_with('0') && digits.len() > 1)
+        {
+            return Ok(answer);
+        }
+        let num = text
+            .parse()
+            .map_err(|_| anyhow!("{text} doesn't fit in an i128, it can't be an AoC answer"))?;
+        Ok(Answer::Integer(num))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::classify(text)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::classify(&text)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(num) => num.fmt(f),
+            Answer::Text(text) | Answer::MultiLine(text) => text.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleAnswer(pub Option<Answer>, pub Option<Answer>);
 
 pub trait AsAnswer {
-    fn as_answer(self) -> Option<String>;
+    fn as_answer(self) -> Option<Answer>;
 }
 
-macro_rules! to_string_conv {
+macro_rules! into_answer {
+    ($ty:ty $(, $param:ident)?) => {
+        impl$(<$param: AsAnswer>)? From<$ty> for PuzzleAnswer {
+            fn from(val: $ty) -> Self {
+                PuzzleAnswer(val.as_answer(), None)
+            }
+        }
+    };
+}
+
+macro_rules! int_conv {
     ($ty:ty) => {
         impl AsAnswer for $ty {
-            fn as_answer(self) -> Option<String> {
-                Some(self.to_string())
+            fn as_answer(self) -> Option<Answer> {
+                match i128::try_from(self) {
+                    Ok(num) => Some(Answer::Integer(num)),
+                    Err(_) => {
+                        eprintln!(
+                            "Answer {self} doesn't fit in an i128, it can't be an AoC answer"
+                        );
+                        None
+                    }
+                }
             }
         }
-        impl AsAnswer for Option<$ty> {
-            fn as_answer(self) -> Option<String> {
-                self.map(|x| x.to_string())
+        into_answer!($ty);
+    };
+}
+
+macro_rules! text_conv {
+    ($ty:ty) => {
+        impl AsAnswer for $ty {
+            fn as_answer(self) -> Option<Answer> {
+                Some(Answer::classify(&self.to_string()))
             }
         }
-        impl Into<PuzzleAnswer> for $ty {
-            fn into(self) -> PuzzleAnswer {
-                PuzzleAnswer(self.as_answer(), None)
+        into_answer!($ty);
+    };
+}
+
+int_conv!(usize);
+int_conv!(isize);
+int_conv!(u8);
+int_conv!(i8);
+int_conv!(u16);
+int_conv!(i16);
+int_conv!(u32);
+int_conv!(i32);
+int_conv!(u64);
+int_conv!(i64);
+int_conv!(u128);
+int_conv!(i128);
+macro_rules! str_conv {
+    ($ty:ty) => {
+        impl AsAnswer for $ty {
+            fn as_answer(self) -> Option<Answer> {
+                self.parse()
+                    .map_err(|err| eprintln!("Answer {self}: {err}"))
+                    .ok()
             }
         }
+        into_answer!($ty);
     };
 }
 
-to_string_conv!(usize);
-to_string_conv!(u8);
-to_string_conv!(i8);
-to_string_conv!(u16);
-to_string_conv!(i16);
-to_string_conv!(u32);
-to_string_conv!(i32);
-to_string_conv!(u64);
-to_string_conv!(i64);
-to_string_conv!(u128);
-to_string_conv!(i128);
-to_string_conv!(&str);
-to_string_conv!(String);
-
-impl<T: AsAnswer, J: AsAnswer> Into<PuzzleAnswer> for (T, J) {
-    fn into(self) -> PuzzleAnswer {
-        let (a, b) = self;
+text_conv!(char);
+text_conv!(bool);
+str_conv!(&str);
+str_conv!(String);
+
+impl AsAnswer for BigInt {
+    fn as_answer(self) -> Option<Answer> {
+        match i128::try_from(&self) {
+            Ok(num) => Some(Answer::Integer(num)),
+            Err(_) => {
+                eprintln!("Answer {self} doesn't fit in an i128, it can't be an AoC answer");
+                None
+            }
+        }
+    }
+}
+into_answer!(BigInt);
+
+/// How a float is turned into an answer, AoC never accepts a decimal point
+/// unless the puzzle asks for one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Nearest,
+    Floor,
+    Ceil,
+    Decimals(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rounded(pub f64, pub Rounding);
+
+impl AsAnswer for Rounded {
+    fn as_answer(self) -> Option<Answer> {
+        let Rounded(val, rounding) = self;
+        if !val.is_finite() {
+            eprintln!("Answer {val} is not a finite number");
+            return None;
+        }
+        let text = match rounding {
+            Rounding::Nearest => format!("{}", val.round()),
+            Rounding::Floor => format!("{}", val.floor()),
+            Rounding::Ceil => format!("{}", val.ceil()),
+            Rounding::Decimals(places) => return Some(Answer::text(&format!("{val:.places$}"))),
+        };
+        text.parse()
+            .map_err(|err| eprintln!("Answer {val}: {err}"))
+            .ok()
+    }
+}
+
+/// Plain floats round to the nearest integer, use `Rounded` to pick another policy.
+impl AsAnswer for f64 {
+    fn as_answer(self) -> Option<Answer> {
+        Rounded(self, Rounding::Nearest).as_answer()
+    }
+}
+
+/// A list of values joined with a separator, plain `Vec`s and slices use a comma.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Joined<T>(pub Vec<T>, pub &'static str);
+
+impl<T: AsAnswer> AsAnswer for Joined<T> {
+    fn as_answer(self) -> Option<Answer> {
+        let Joined(items, sep) = self;
+        if items.is_empty() {
+            return None;
+        }
+        let items: Option<Vec<Answer>> = items.into_iter().map(AsAnswer::as_answer).collect();
+        items.map(|items| Answer::text(&items.iter().join(sep)))
+    }
+}
+
+impl<T: AsAnswer> AsAnswer for Vec<T> {
+    fn as_answer(self) -> Option<Answer> {
+        Joined(self, ",").as_answer()
+    }
+}
+
+impl<T: AsAnswer + Clone> AsAnswer for &[T] {
+    fn as_answer(self) -> Option<Answer> {
+        self.to_vec().as_answer()
+    }
+}
+
+impl<T: AsAnswer> AsAnswer for Option<T> {
+    fn as_answer(self) -> Option<Answer> {
+        self.and_then(AsAnswer::as_answer)
+    }
+}
+
+/// A part that failed to solve shouldn't stop the other part being reported.
+impl<T: AsAnswer> AsAnswer for anyhow::Result<T> {
+    fn as_answer(self) -> Option<Answer> {
+        match self {
+            Ok(val) => val.as_answer(),
+            Err(err) => {
+                eprintln!("No answer: {err:#}");
+                None
+            }
+        }
+    }
+}
+
+into_answer!(f64);
+into_answer!(Rounded);
+into_answer!(Joined<T>, T);
+into_answer!(Vec<T>, T);
+into_answer!(Option<T>, T);
+into_answer!(anyhow::Result<T>, T);
+
+impl<T: AsAnswer, J: AsAnswer> From<(T, J)> for PuzzleAnswer {
+    fn from((a, b): (T, J)) -> Self {
         PuzzleAnswer(a.as_answer(), b.as_answer())
     }
 }
+
+#[test]
+pub fn answer_conversions() {
+    assert_eq!('x'.as_answer(), Some("x".into()));
+    assert_eq!(true.as_answer(), Some("true".into()));
+    assert_eq!(2.5f64.as_answer(), Some("3".into()));
+    assert_eq!(Rounded(2.5, Rounding::Floor).as_answer(), Some("2".into()));
+    assert_eq!(
+        Rounded(1.0 / 3.0, Rounding::Decimals(2)).as_answer(),
+        Some("0.33".into())
+    );
+    assert_eq!(f64::NAN.as_answer(), None);
+    assert_eq!(vec![4, 6, 3].as_answer(), Some("4,6,3".into()));
+    assert_eq!(Joined(vec!['a', 'b'], "").as_answer(), Some("ab".into()));
+    assert_eq!([1u8, 2][..].as_answer(), Some("1,2".into()));
+    assert_eq!(Vec::<i32>::new().as_answer(), None);
+    assert_eq!(
+        (BigInt::from(i128::MAX) - BigInt::from(1)).as_answer(),
+        Some(Answer::Integer(i128::MAX - 1))
+    );
+    assert_eq!(
+        (BigInt::from(u128::MAX) * BigInt::from(2)).as_answer(),
+        None
+    );
+    assert_eq!(u128::MAX.as_answer(), None);
+    assert_eq!("4217".as_answer(), Some(Answer::Integer(4217)));
+    assert_eq!(u128::MAX.to_string().as_answer(), None);
+    assert_eq!(String::from("0042").as_answer(), Some(Answer::text("0042")));
+
+    let answer: PuzzleAnswer =
+        (Some(5), anyhow::Result::<i32>::Err(anyhow::anyhow!("nope"))).into();
+    assert_eq!(answer, PuzzleAnswer(Some("5".into()), None));
+    let answer: PuzzleAnswer = Some(Some(7)).into();
+    assert_eq!(answer, PuzzleAnswer(Some("7".into()), None));
+}
+
+#[test]
+pub fn typed_comparison() {
+    assert_eq!("42".parse::<Answer>().unwrap(), Answer::Integer(42));
+    assert_eq!("+42 \n".parse::<Answer>().unwrap(), Answer::Integer(42));
+    assert_eq!(
+        "0042".parse::<Answer>().unwrap(),
+        Answer::Text("0042".to_owned())
+    );
+    assert_ne!("0042".parse::<Answer>().unwrap(), Answer::Integer(42));
+    assert!("999999999999999999999999999999999999999999"
+        .parse::<Answer>()
+        .is_err());
+
+    let art = Answer::text("\n#..#  \n####\n\n");
+    assert_eq!(art, Answer::MultiLine("#..#\n####".to_owned()));
+    assert_eq!(art, "#..#\n####\n".parse().unwrap());
+    assert_eq!(art.as_int(), None);
+    assert_eq!(Answer::text("abc "), Answer::from("abc"));
+}
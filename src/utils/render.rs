@@ -0,0 +1,181 @@
+use super::GridLike;
+use anyhow::{Context, Result};
+use glam::IVec2;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::fs::{create_dir_all, File};
+use std::path::Path;
+use std::time::Duration;
+
+pub use image::Rgba;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Pixels per cell along each side.
+    pub scale: u32,
+    /// Blank pixels around the whole image.
+    pub padding: u32,
+    /// Draws a one pixel line between cells in this color.
+    pub grid_lines: Option<Rgba<u8>>,
+    /// Used for the padding and for cells missing from the grid.
+    pub background: Rgba<u8>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            scale: 4,
+            padding: 0,
+            grid_lines: None,
+            background: Rgba([0, 0, 0, 255]),
+        }
+    }
+}
+
+/// Draws every cell inside the grid's bounds with the color `color` picks for it.
+pub fn render<G: GridLike>(
+    grid: &G,
+    options: &RenderOptions,
+    color: impl Fn(IVec2, &G::Cell) -> Rgba<u8>,
+) -> RgbaImage {
+    let bounds = grid.bounds().unwrap_or((IVec2::ZERO, -IVec2::ONE));
+    render_bounds(grid, bounds, options, color)
+}
+
+fn render_bounds<G: GridLike>(
+    grid: &G,
+    (min, max): (IVec2, IVec2),
+    options: &RenderOptions,
+    color: impl Fn(IVec2, &G::Cell) -> Rgba<u8>,
+) -> RgbaImage {
+    let cells = (max - min + IVec2::ONE).max(IVec2::ZERO);
+    let line = options.grid_lines.is_some() as u32;
+    let pitch = options.scale + line;
+    let size = |cells: i32| cells as u32 * pitch + line + 2 * options.padding;
+    RgbaImage::from_fn(size(cells.x), size(cells.y), |x, y| {
+        let (x, y) = (
+            x.wrapping_sub(options.padding),
+            y.wrapping_sub(options.padding),
+        );
+        let inside = |v: u32, cells: i32| v < cells as u32 * pitch + line;
+        if !inside(x, cells.x) || !inside(y, cells.y) {
+            return options.background;
+        }
+        if let Some(line_color) = options.grid_lines {
+            if x % pitch == 0 || y % pitch == 0 {
+                return line_color;
+            }
+        }
+        let pos = min + IVec2::new(((x - line) / pitch) as i32, ((y - line) / pitch) as i32);
+        grid.get(pos)
+            .map_or(options.background, |cell| color(pos, cell))
+    })
+}
+
+/// Saves an image as a PNG, creating the directory it goes in if needed.
+pub fn save_png(image: &RgbaImage, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir)?;
+    }
+    image
+        .save(path)
+        .with_context(|| format!("Saving {}", path.display()))
+}
+
+/// Collects rendered frames of a simulation and writes them out as a looping GIF.
+pub struct FrameRecorder {
+    pub options: RenderOptions,
+    frame_time: Duration,
+    bounds: Option<(IVec2, IVec2)>,
+    frames: Vec<RgbaImage>,
+}
+
+impl FrameRecorder {
+    pub fn new(options: RenderOptions, frame_time: Duration) -> Self {
+        FrameRecorder {
+            options,
+            frame_time,
+            bounds: None,
+            frames: vec![],
+        }
+    }
+
+    /// Every frame of a GIF is the same size, by default it's the bounds of the first frame.
+    pub fn with_bounds(mut self, min: IVec2, max: IVec2) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    pub fn record<G: GridLike>(&mut self, grid: &G, color: impl Fn(IVec2, &G::Cell) -> Rgba<u8>) {
+        let bounds = *self
+            .bounds
+            .get_or_insert_with(|| grid.bounds().unwrap_or((IVec2::ZERO, -IVec2::ONE)));
+        self.frames
+            .push(render_bounds(grid, bounds, &self.options, color));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Consumes the recorder so the frames can be handed to the encoder without copying.
+    pub fn save_gif(self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        let file = File::create(path).with_context(|| format!("Creating {}", path.display()))?;
+        let mut encoder = GifEncoder::new(file);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = Delay::from_saturating_duration(self.frame_time);
+        encoder.encode_frames(
+            self.frames
+                .into_iter()
+                .map(|frame| Frame::from_parts(frame, 0, 0, delay)),
+        )?;
+        Ok(())
+    }
+}
+
+#[test]
+pub fn render_cells() -> Result<()> {
+    use hashbrown::HashSet;
+
+    let white = Rgba([255, 255, 255, 255]);
+    let red = Rgba([255, 0, 0, 255]);
+    let set: HashSet<IVec2> = [IVec2::new(0, 0), IVec2::new(2, 1)].into_iter().collect();
+    let options = RenderOptions {
+        scale: 2,
+        padding: 1,
+        grid_lines: Some(red),
+        ..Default::default()
+    };
+    let image = render(&set, &options, |_, _| white);
+    // 3 cells of 2px, 4 lines and 1px of padding either side.
+    assert_eq!(image.dimensions(), (3 * 2 + 4 + 2, 2 * 2 + 3 + 2));
+    assert_eq!(*image.get_pixel(0, 0), options.background);
+    assert_eq!(*image.get_pixel(1, 1), red);
+    assert_eq!(*image.get_pixel(2, 2), white);
+    assert_eq!(*image.get_pixel(5, 2), options.background);
+    assert_eq!(*image.get_pixel(9, 5), white);
+
+    let plain = render(&set, &RenderOptions::default(), |_, _| white);
+    assert_eq!(plain.dimensions(), (12, 8));
+
+    let dir = std::env::temp_dir().join("aoc_render_test");
+    save_png(&image, dir.join("cells.png"))?;
+    let mut recorder = FrameRecorder::new(options, Duration::from_millis(100));
+    for i in 0..3 {
+        let moved: HashSet<IVec2> = set.iter().map(|pos| *pos + IVec2::new(i % 2, 0)).collect();
+        recorder.record(&moved, |_, _| white);
+    }
+    assert_eq!(recorder.len(), 3);
+    recorder.save_gif(dir.join("cells.gif"))?;
+    assert!(std::fs::read(dir.join("cells.gif"))?.starts_with(b"GIF89a"));
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Once,
+    },
+    time::Duration,
+};
+
+use super::{
+    aocdata::Part,
+    puzzle::{PuzzleDay, PuzzleYear},
+    puzzleanswer::{Answer, PuzzleAnswer},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed { expected: String, actual: String },
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub index: usize,
+    pub part: Part,
+    pub outcome: TestOutcome,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestReport {
+    pub id: (PuzzleYear, PuzzleDay),
+    pub results: Vec<TestResult>,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> bool {
+        self.results
+            .iter()
+            .all(|result| result.outcome == TestOutcome::Passed)
+    }
+
+    pub fn failures(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.outcome != TestOutcome::Passed)
+            .count()
+    }
+}
+
+/// Line by line comparison, good enough for the small grids and lists AoC answers are made of.
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => diff.push_str(&format!("  {e}\n")),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push_str(&format!("- {e}\n"));
+                }
+                if let Some(a) = a {
+                    diff.push_str(&format!("+ {a}\n"));
+                }
+            }
+        }
+    }
+    diff
+}
+
+impl Display for TestResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = format!("part {:?} example {}", self.part, self.index);
+        let millis = self.duration.as_secs_f64() * 1000.;
+        match &self.outcome {
+            TestOutcome::Passed => writeln!(f, "  ok    {name} ({millis:.2} ms)"),
+            TestOutcome::Error(err) => writeln!(f, "  ERROR {name} ({millis:.2} ms): {err}"),
+            TestOutcome::Failed { expected, actual } => {
+                writeln!(f, "  FAIL  {name} ({millis:.2} ms)")?;
+                if expected.contains('\n') || actual.contains('\n') {
+                    writeln!(f, "    --- expected\n    +++ actual")?;
+                    for line in line_diff(expected, actual).lines() {
+                        writeln!(f, "    {line}")?;
+                    }
+                    Ok(())
+                } else {
+                    writeln!(f, "    expected: {expected}")?;
+                    writeln!(f, "    actual:   {actual}")
+                }
+            }
+        }
+    }
+}
+
+impl Display for TestReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, day) = self.id;
+        writeln!(f, "{year} day {day}")?;
+        for result in &self.results {
+            result.fmt(f)?;
+        }
+        writeln!(
+            f,
+            "  {} passed, {} failed",
+            self.results.len() - self.failures(),
+            self.failures()
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunOutcome {
+    Solved(PuzzleAnswer),
+    Error(String),
+    TimedOut(Duration),
+    Panicked(PanicReport),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into a report of its message and location instead of unwinding
+/// into the caller. The panic isn't printed, the report is expected to end up in the results.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, PanicReport> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) {
+                let location = info.location().map(ToString::to_string);
+                PANIC_LOCATION.with(|loc| *loc.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(was_catching));
+    res.map_err(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unknown panic payload".to_owned()
+        };
+        PanicReport {
+            message,
+            location: PANIC_LOCATION.with(|loc| loc.borrow_mut().take()),
+        }
+    })
+}
+
+/// Set by the runner when it gives up on a solver. Threads can't be killed, so solvers with
+/// long loops should check it and return early, otherwise they keep running in the background.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A solver run on the real input, benchmarks keep the duration of every iteration.
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub id: (PuzzleYear, PuzzleDay),
+    pub outcome: RunOutcome,
+    pub durations: Vec<Duration>,
+}
+
+impl RunResult {
+    pub fn mean(&self) -> Duration {
+        let total: Duration = self.durations.iter().sum();
+        total / self.durations.len().max(1) as u32
+    }
+
+    pub fn min(&self) -> Duration {
+        self.durations.iter().min().copied().unwrap_or_default()
+    }
+}
+
+impl Display for RunResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, day) = self.id;
+        write!(f, "{year} day {day:>2}  ")?;
+        match &self.outcome {
+            RunOutcome::Solved(PuzzleAnswer(a, b)) => {
+                let show = |answer: &Option<_>| match answer {
+                    // Drawn answers would break the table, OCR them in the solver instead.
+                    Some(Answer::MultiLine(text)) => format!("<{} lines>", text.lines().count()),
+                    Some(answer) => answer.to_string(),
+                    None => "-".to_owned(),
+                };
+                write!(f, "a: {:<20} b: {:<20}", show(a), show(b))?;
+            }
+            RunOutcome::Error(err) => write!(f, "ERROR {err}  ")?,
+            RunOutcome::TimedOut(timeout) => {
+                write!(f, "timed out after {:.1}s  ", timeout.as_secs_f64())?
+            }
+            RunOutcome::Panicked(report) => write!(f, "PANIC {report}  ")?,
+        }
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.;
+        if self.durations.len() > 1 {
+            writeln!(
+                f,
+                " mean {:.2} ms, min {:.2} ms over {} runs",
+                millis(self.mean()),
+                millis(self.min()),
+                self.durations.len()
+            )
+        } else {
+            writeln!(f, " {:.2} ms", millis(self.mean()))
+        }
+    }
+}
+
+#[test]
+pub fn catches_panics() {
+    assert_eq!(catch_panic(|| 5), Ok(5));
+    let report = catch_panic(|| -> i32 { panic!("bad input {}", 7) }).unwrap_err();
+    assert_eq!(report.message, "bad input 7");
+    assert!(report.location.unwrap().starts_with("src/utils/runner.rs:"));
+    // A panic from inside std still reports the caller's line.
+    let report = catch_panic(|| std::hint::black_box(None::<i32>).unwrap()).unwrap_err();
+    assert!(report.message.contains("None"));
+    assert!(report.location.unwrap().starts_with("src/utils/runner.rs:"));
+}
+
+#[test]
+pub fn diff_multiline() {
+    assert_eq!(line_diff("#.#\n.#.", "#.#\n###"), "  #.#\n- .#.\n+ ###\n");
+    assert_eq!(line_diff("a", "a\nb"), "  a\n+ b\n");
+}
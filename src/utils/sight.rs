@@ -0,0 +1,176 @@
+use super::GridLike;
+use glam::IVec2;
+use itertools::Itertools;
+use num::Integer;
+use std::collections::HashMap;
+
+/// The smallest step in the same direction, so `(4, -6)` becomes `(2, -3)`.
+pub fn reduce_direction(dir: IVec2) -> IVec2 {
+    let divisor = dir.x.gcd(&dir.y);
+    if divisor == 0 {
+        dir
+    } else {
+        dir / divisor
+    }
+}
+
+/// Steps from `from` in `dir` until `hit` accepts a cell, giving up once the ray leaves
+/// the grid's bounds.
+pub fn cast_ray<G: GridLike>(
+    grid: &G,
+    from: IVec2,
+    dir: IVec2,
+    hit: impl Fn(IVec2, &G::Cell) -> bool,
+) -> Option<IVec2> {
+    let (min, max) = grid.bounds()?;
+    if dir == IVec2::ZERO {
+        return None;
+    }
+    let mut pos = from + dir;
+    while pos.cmpge(min).all() && pos.cmple(max).all() {
+        if grid.get(pos).is_some_and(|cell| hit(pos, cell)) {
+            return Some(pos);
+        }
+        pos += dir;
+    }
+    None
+}
+
+/// The closest cell `is_target` accepts in every direction from `from`, cells behind it in
+/// the same reduced direction are hidden.
+pub fn visible<G: GridLike>(
+    grid: &G,
+    from: IVec2,
+    is_target: impl Fn(IVec2, &G::Cell) -> bool,
+) -> Vec<IVec2> {
+    let mut closest: HashMap<IVec2, IVec2> = HashMap::new();
+    for (pos, cell) in grid.iter() {
+        if pos == from || !is_target(pos, cell) {
+            continue;
+        }
+        let dir = reduce_direction(pos - from);
+        let nearer =
+            |other: &IVec2| (*other - from).abs().max_element() < (pos - from).abs().max_element();
+        if !closest.get(&dir).is_some_and(nearer) {
+            closest.insert(dir, pos);
+        }
+    }
+    closest.into_values().collect()
+}
+
+/// The angle of `pos` around `center`, clockwise from up with y growing downwards like the
+/// puzzle text. Straight up is 0.
+pub fn clockwise_angle(center: IVec2, pos: IVec2) -> f64 {
+    let rel = pos - center;
+    let angle = f64::atan2(rel.x as f64, -rel.y as f64);
+    if angle < 0. {
+        angle + std::f64::consts::TAU
+    } else {
+        angle
+    }
+}
+
+/// Sorts cells by their angle around `center`, nearer cells first when they line up. Cells on
+/// the same line share a key since the angle comes from their reduced direction.
+pub fn sort_clockwise(center: IVec2, cells: &mut [IVec2]) {
+    let angle = |pos: &IVec2| clockwise_angle(IVec2::ZERO, reduce_direction(*pos - center));
+    let dist = |pos: &IVec2| (*pos - center).abs().max_element();
+    cells.sort_by(|a, b| {
+        angle(a)
+            .total_cmp(&angle(b))
+            .then_with(|| dist(a).cmp(&dist(b)))
+    });
+}
+
+/// The order a beam sweeping clockwise from up would hit the cells, taking only the nearest
+/// cell in each direction on every turn.
+pub fn sweep_order(center: IVec2, cells: &[IVec2]) -> Vec<IVec2> {
+    let mut lines: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
+    for pos in cells.iter().filter(|pos| **pos != center) {
+        lines
+            .entry(reduce_direction(*pos - center))
+            .or_default()
+            .push(*pos);
+    }
+    let mut lines = lines
+        .into_iter()
+        .map(|(dir, mut line)| {
+            sort_clockwise(center, &mut line);
+            (dir, line.into_iter())
+        })
+        .collect_vec();
+    lines.sort_by(|(a, _), (b, _)| {
+        clockwise_angle(IVec2::ZERO, *a).total_cmp(&clockwise_angle(IVec2::ZERO, *b))
+    });
+    let mut order = vec![];
+    while !lines.is_empty() {
+        lines.retain_mut(|(_, line)| match line.next() {
+            Some(pos) => {
+                order.push(pos);
+                true
+            }
+            None => false,
+        });
+    }
+    order
+}
+
+#[test]
+pub fn line_of_sight() {
+    use hashbrown::HashSet;
+
+    assert_eq!(reduce_direction(IVec2::new(4, -6)), IVec2::new(2, -3));
+    assert_eq!(reduce_direction(IVec2::new(0, -5)), IVec2::new(0, -1));
+
+    let asteroids: HashSet<IVec2> = ".#..#\n.....\n#####\n....#\n...##"
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| IVec2::new(x as i32, y as i32))
+        })
+        .collect();
+    let best = asteroids
+        .iter()
+        .max_by_key(|a| visible(&asteroids, **a, |_, _| true).len())
+        .unwrap();
+    assert_eq!(*best, IVec2::new(3, 4));
+    assert_eq!(visible(&asteroids, *best, |_, _| true).len(), 8);
+    assert_eq!(
+        cast_ray(&asteroids, IVec2::new(4, 0), IVec2::new(0, 1), |_, _| true),
+        Some(IVec2::new(4, 2))
+    );
+    assert_eq!(
+        cast_ray(&asteroids, IVec2::new(4, 0), IVec2::new(1, 0), |_, _| true),
+        None
+    );
+
+    let center = IVec2::new(2, 2);
+    let mut around = vec![
+        IVec2::new(1, 2),
+        IVec2::new(2, 0),
+        IVec2::new(2, 3),
+        IVec2::new(3, 2),
+        IVec2::new(2, 1),
+    ];
+    sort_clockwise(center, &mut around);
+    let expected = [(2, 1), (2, 0), (3, 2), (2, 3), (1, 2)].map(|(x, y)| IVec2::new(x, y));
+    assert_eq!(around, expected);
+    let expected = [(2, 1), (3, 2), (2, 3), (1, 2), (2, 0)].map(|(x, y)| IVec2::new(x, y));
+    assert_eq!(sweep_order(center, &around), expected);
+
+    // Far along a shallow line, float angles of the unreduced offsets needn't match exactly.
+    let line = (1..=40).map(|k| IVec2::new(3 * k, -7 * k)).collect_vec();
+    let mut cells = line
+        .iter()
+        .rev()
+        .copied()
+        .chain([IVec2::new(1, -2)])
+        .collect_vec();
+    let order = sweep_order(IVec2::ZERO, &cells);
+    assert_eq!(&order[..2], &[IVec2::new(3, -7), IVec2::new(1, -2)]);
+    sort_clockwise(IVec2::ZERO, &mut cells);
+    assert_eq!(&cells[..40], &line[..]);
+}
@@ -1,4 +1,4 @@
-use super::{djikstra, djikstra_path, get_cardinal_neighbors, read_lines};
+use super::{get_cardinal_neighbors, GridLike};
 use anyhow::{anyhow, Error};
 pub use glam::{IVec2, IVec3, Quat, Vec2, Vec3};
 use hashbrown::{HashMap, HashSet};
@@ -21,192 +21,617 @@ pub struct World<T> {
     pub world: HashMap<IVec2, T>,
 }
 
+/// How each line of the input is split into cells, empty cells are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tokens {
+    /// One cell per character.
+    Chars,
+    /// Cells separated by runs of whitespace, like rows of numbers.
+    Whitespace,
+    Separator(&'static str),
+    /// Cells that are a fixed number of characters wide.
+    Width(usize),
+}
+
+impl Tokens {
+    /// The non-empty cells of a line as `(x, column, cell)`, x counts empty cells between
+    /// separators so later cells stay in place and the column counts characters from 1.
+    pub fn split<'a>(&self, line: &'a str) -> Vec<(usize, usize, &'a str)> {
+        let column = |token: &str| {
+            let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+            line[..offset].chars().count() + 1
+        };
+        let tokens: Vec<&str> = match self {
+            Tokens::Chars => line
+                .char_indices()
+                .map(|(i, c)| &line[i..i + c.len_utf8()])
+                .collect(),
+            Tokens::Whitespace => line.split_whitespace().collect(),
+            Tokens::Separator(sep) => line.split(sep).collect(),
+            Tokens::Width(width) => {
+                let starts = line.char_indices().map(|(i, _)| i).step_by(*width.max(&1));
+                let ends = starts.clone().skip(1).chain([line.len()]);
+                starts.zip(ends).map(|(a, b)| &line[a..b]).collect()
+            }
+        };
+        tokens
+            .into_iter()
+            .enumerate()
+            .filter(|(_, token)| !token.is_empty())
+            .map(|(x, token)| (x, column(token), token))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YAxis {
+    /// The first line is y = 0, like the puzzle text.
+    #[default]
+    Down,
+    /// The last line is y = 0 and y grows towards the top.
+    Up,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub tokens: Tokens,
+    /// Where the first cell of the y = 0 line is placed.
+    pub origin: IVec2,
+    pub y_axis: YAxis,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            tokens: Tokens::Chars,
+            origin: IVec2::ZERO,
+            y_axis: YAxis::Down,
+        }
+    }
+}
+
 impl<T> TryFrom<&str> for World<T>
 where
     T: TryFrom<char>,
-    <T as TryFrom<char>>::Error: Debug,
+    <T as TryFrom<char>>::Error: Display,
 {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(World::<T>::from_str(value, |x| {
-            let c: char = x.chars().nth(0).unwrap();
-            let t: T = c.try_into().unwrap();
-            t
-        }))
+        World::parse(value, &ParseOptions::default(), |token| {
+            // Char tokens are never empty.
+            let c = token.chars().next().unwrap();
+            T::try_from(c).map_err(|err| anyhow!("{err}"))
+        })
     }
 }
 
 impl<T> World<T> {
     pub fn from_file<U: Into<Option<T>>>(path: &str, parse_fn: impl Fn(&str) -> U) -> Option<Self> {
-        if let Ok(lines) = read_lines(path) {
-            let mut world = HashMap::new();
-            for (y, line) in lines.enumerate() {
-                if let Ok(contents) = line {
-                    for (x, height) in contents
-                        .split("")
-                        .filter(|s| s.len() > 0)
-                        .map(&parse_fn)
-                        .map(|x| {
-                            let x: Option<T> = x.into();
-                            x
-                        })
-                        .enumerate()
-                        .filter(|(_i, x)| x.is_some())
-                        .map(|(i, x)| (i, x.unwrap()))
-                    {
-                        let pos = IVec2::new(x as i32, y as i32);
-                        world.insert(pos, height);
-                    }
-                }
-            }
-            Some(World { world })
-        } else {
-            None
-        }
+        let input = std::fs::read_to_string(path).ok()?;
+        Some(Self::from_str(&input, parse_fn))
     }
 
     pub fn from_str<U: Into<Option<T>>>(input: &str, parse_fn: impl Fn(&str) -> U) -> Self {
-        let mut world = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, height) in line
-                .split("")
-                .filter(|s| s.len() > 0)
-                .map(&parse_fn)
-                .map(|x| {
-                    let x: Option<T> = x.into();
-                    x
-                })
-                .enumerate()
-                .filter(|(_i, x)| x.is_some())
-                .map(|(i, x)| (i, x.unwrap()))
-            {
-                let pos = IVec2::new(x as i32, y as i32);
-                world.insert(pos, height);
-            }
-        }
-        World { world }
+        Self::parse(input, &ParseOptions::default(), |token| {
+            Ok::<_, Error>(parse_fn(token))
+        })
+        .unwrap()
     }
 
-    pub fn pretty_print(&self, rev_y: bool)
+    /// Parses every cell of the input, cells that parse to `None` are left out of the world.
+    /// Errors name the line and column of the cell that failed.
+    pub fn parse<U: Into<Option<T>>>(
+        input: &str,
+        options: &ParseOptions,
+        parse_fn: impl Fn(&str) -> anyhow::Result<U>,
+    ) -> anyhow::Result<Self> {
+        Self::parse_cells(input, options, |_, token| Ok(parse_fn(token)?.into()))
+    }
+
+    /// Like `parse`, but also records where the single character tokens picked out by
+    /// `is_marker` are. Given a `base` tile, markers are stored as that instead of being parsed.
+    pub fn parse_markers<U: Into<Option<T>>>(
+        input: &str,
+        options: &ParseOptions,
+        is_marker: impl Fn(char) -> bool,
+        base: Option<T>,
+        parse_fn: impl Fn(&str) -> anyhow::Result<U>,
+    ) -> anyhow::Result<(Self, Markers)>
     where
-        T: Display,
+        T: Clone,
     {
-        self.pretty_print_custom(|x, _| x.to_string(), rev_y)
-    }
-
-    pub fn pretty_print_custom(&self, str_fn: impl Fn(&T, IVec2) -> String, rev_y: bool) {
-        let max_y = self.max_y();
-        let max_x = self.max_x();
-        let min_x = self.min_x().min(0);
-        let min_y = self.min_y().min(0);
-
-        if rev_y {
-            for y in (min_y..=max_y).rev() {
-                let mut row = vec![];
-                for x in min_x..=max_x {
-                    let pos = IVec2::new(x, y);
-                    if let Some(val) = self.world.get(&pos) {
-                        row.push(str_fn(val, pos));
-                    } else {
-                        row.push(" ".to_string());
+        let mut markers = Markers::default();
+        let world = Self::parse_cells(input, options, |pos, token| {
+            let mut chars = token.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if is_marker(c) {
+                    markers.0.entry(c).or_default().push(pos);
+                    if let Some(base) = &base {
+                        return Ok(Some(base.clone()));
                     }
                 }
-                println!("{}", row.iter().join(""));
             }
-        } else {
-            for y in min_y..=max_y {
-                let mut row = vec![];
-                for x in min_x..=max_x {
-                    let pos = IVec2::new(x, y);
-                    if let Some(val) = self.world.get(&pos) {
-                        row.push(str_fn(val, pos));
-                    } else {
-                        row.push(" ".to_string());
-                    }
+            Ok(parse_fn(token)?.into())
+        })?;
+        Ok((world, markers))
+    }
+
+    fn parse_cells(
+        input: &str,
+        options: &ParseOptions,
+        mut parse_fn: impl FnMut(IVec2, &str) -> anyhow::Result<Option<T>>,
+    ) -> anyhow::Result<Self> {
+        let lines = input.lines().collect_vec();
+        let mut world = HashMap::new();
+        for (row, line) in lines.iter().enumerate() {
+            let y = match options.y_axis {
+                YAxis::Down => row,
+                YAxis::Up => lines.len() - 1 - row,
+            };
+            for (x, column, token) in options.tokens.split(line) {
+                let pos = options.origin + IVec2::new(x as i32, y as i32);
+                let cell = parse_fn(pos, token).map_err(|err| {
+                    anyhow!("Line {}, column {column}: {token:?}: {err:#}", row + 1)
+                })?;
+                if let Some(cell) = cell {
+                    world.insert(pos, cell);
                 }
-                println!("{}", row.iter().join(""));
             }
         }
+        Ok(World { world })
+    }
+}
+
+/// Where each marker was found while parsing, in the order they were read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers(pub HashMap<char, Vec<IVec2>>);
+
+impl Markers {
+    pub fn all(&self, marker: char) -> &[IVec2] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The position of a marker that should appear exactly once, like a start tile.
+    pub fn one(&self, marker: char) -> anyhow::Result<IVec2> {
+        match self.all(marker) {
+            [pos] => Ok(*pos),
+            positions => Err(anyhow!(
+                "Expected one {marker:?} but found {}",
+                positions.len()
+            )),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, IVec2)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|(marker, positions)| positions.iter().map(move |pos| (*marker, *pos)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl Axis {
+    /// The component of an `IVec2` that lies along the axis.
+    pub fn index(&self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+        }
+    }
+}
+
+/// Clockwise quarter turns, with y growing downwards like the puzzle text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    R90,
+    R180,
+    R270,
+}
+
+impl Rotation {
+    /// Where `rel`, relative to the min corner of a box of `size`, ends up after turning the box.
+    pub fn apply(&self, rel: IVec2, size: IVec2) -> IVec2 {
+        match self {
+            Rotation::R90 => IVec2::new(size.y - 1 - rel.y, rel.x),
+            Rotation::R180 => size - IVec2::ONE - rel,
+            Rotation::R270 => IVec2::new(rel.y, size.x - 1 - rel.x),
+        }
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        match self {
+            Rotation::R90 => Rotation::R270,
+            Rotation::R180 => Rotation::R180,
+            Rotation::R270 => Rotation::R90,
+        }
+    }
+}
+
+/// The transforms work on the occupied bounds, the min corner stays where it was. `Grid` has
+/// the same set with the same signatures.
+impl<T: Clone> World<T> {
+    fn remap(&self, f: impl Fn(IVec2, IVec2) -> IVec2) -> Self {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return self.clone(),
+        };
+        let size = max - min + IVec2::ONE;
+        World {
+            world: self
+                .world
+                .iter()
+                .map(|(pos, val)| (min + f(*pos - min, size), val.clone()))
+                .collect(),
+        }
     }
 
-    pub fn max_x(&self) -> i32 {
-        self.world.keys().map(|pos| pos.x).max().unwrap()
+    pub fn rotate(&self, rotation: Rotation) -> Self {
+        self.remap(|rel, size| rotation.apply(rel, size))
     }
-    pub fn max_y(&self) -> i32 {
-        self.world.keys().map(|pos| pos.y).max().unwrap()
+
+    /// Mirrors the coordinates along `axis`, so `Axis::X` swaps left and right.
+    pub fn flip(&self, axis: Axis) -> Self {
+        self.remap(|rel, size| match axis {
+            Axis::X => IVec2::new(size.x - 1 - rel.x, rel.y),
+            Axis::Y => IVec2::new(rel.x, size.y - 1 - rel.y),
+        })
     }
 
-    pub fn min_x(&self) -> i32 {
-        self.world.keys().map(|pos| pos.x).min().unwrap()
+    pub fn transpose(&self) -> Self {
+        self.remap(|rel, _| IVec2::new(rel.y, rel.x))
     }
-    pub fn min_y(&self) -> i32 {
-        self.world.keys().map(|pos| pos.y).min().unwrap()
+
+    /// The cells inside the inclusive rectangle from `min` to `max`.
+    pub fn crop(&self, min: IVec2, max: IVec2) -> Self {
+        World {
+            world: self
+                .world
+                .iter()
+                .filter(|(pos, _)| pos.cmpge(min).all() && pos.cmple(max).all())
+                .map(|(pos, val)| (*pos, val.clone()))
+                .collect(),
+        }
     }
 
-    /***
-     * Calculates the minimum cost to move from the start position to the state determined by the done fn.
-     * If done never returns true this function returns None. The weight function should return None for
-     * any position that cannot be traversed.
-     */
-    pub fn min_cost_4<S>(
-        &mut self,
-        state: &mut S,
-        start: IVec2,
-        done: impl Fn(IVec2, &mut Self, &mut S, usize) -> bool,
-        get_weight: impl Fn(IVec2, &mut Self, &mut S) -> Option<usize>,
-    ) -> Option<usize> {
-        djikstra(
-            start,
-            0,
-            &mut (self, state),
-            |&pos, cost, (world, extra_state)| done(pos, world, extra_state, cost),
-            |pos, _, (world, extra_state)| {
-                let x: SmallVec<[_; 4]> = get_cardinal_neighbors(*pos)
-                    .iter()
-                    .map(|x| {
-                        if let Some(w) = get_weight(*x, world, extra_state) {
-                            Some((*x, w))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                x
-            },
-        )
-    }
-
-    /***
-     * Calculates the lowest cost path to move from the start position to the state determined by the done fn.
-     * If done never returns true this function returns None. The weight function should return None for
-     * any position that cannot be traversed.
-     */
-    pub fn min_path_4<S>(
-        &mut self,
-        extra_state: &mut S,
-        start: IVec2,
-        done: impl Fn(IVec2, &mut Self, &mut S) -> bool,
-        get_weight: impl Fn(IVec2, &mut Self, &mut S) -> Option<usize>,
-    ) -> Option<(usize, Vec<IVec2>)> {
-        djikstra_path(
-            start,
-            0,
-            &mut (self, extra_state),
-            |&pos, _, (state, extra_state)| done(pos, state, extra_state),
-            |pos, _, (state, extra_state)| {
-                let x: SmallVec<[_; 4]> = get_cardinal_neighbors(*pos)
+    /// Repeats the world `across` times to the right and `down` times downwards. Each copy's
+    /// values come from `mapper`, which is given the tile index, `(0, 0)` being the original.
+    pub fn tile(&self, across: usize, down: usize, mapper: impl Fn(&T, IVec2) -> T) -> Self {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return self.clone(),
+        };
+        let size = max - min + IVec2::ONE;
+        let tiles =
+            (0..down as i32).flat_map(|y| (0..across as i32).map(move |x| IVec2::new(x, y)));
+        let mut world = HashMap::new();
+        for tile in tiles {
+            for (pos, val) in &self.world {
+                world.insert(*pos + tile * size, mapper(val, tile));
+            }
+        }
+        World { world }
+    }
+
+    /// Folds everything past `line` back over it, like folding paper. Cells on the line are
+    /// dropped and `merge` combines the cell that was there with the one folded onto it.
+    pub fn fold(&self, axis: Axis, line: i32, merge: impl Fn(&T, &T) -> T) -> Self {
+        let index = axis.index();
+        let mut world: HashMap<IVec2, T> = self
+            .world
+            .iter()
+            .filter(|(pos, _)| pos[index] < line)
+            .map(|(pos, val)| (*pos, val.clone()))
+            .collect();
+        for (pos, val) in self.world.iter().filter(|(pos, _)| pos[index] > line) {
+            let mut pos = *pos;
+            pos[index] = 2 * line - pos[index];
+            let val = match world.get(&pos) {
+                Some(existing) => merge(existing, val),
+                None => val.clone(),
+            };
+            world.insert(pos, val);
+        }
+        World { world }
+    }
+}
+
+impl<T> GridLike for World<T> {
+    type Cell = T;
+
+    fn get(&self, pos: IVec2) -> Option<&T> {
+        self.world.get(&pos)
+    }
+
+    /// One pass over the keys rather than one per coordinate.
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        let mut keys = self.world.keys();
+        let first = *keys.next()?;
+        Some(keys.fold((first, first), |(min, max), pos| {
+            (min.min(*pos), max.max(*pos))
+        }))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.world.iter().map(|(pos, val)| (*pos, val))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+    /// Only left and right, for runs of cells within a line like the numbers in a schematic.
+    Rows,
+}
+
+impl Connectivity {
+    pub fn neighbors<G: GridLike>(&self, grid: &G, pos: IVec2) -> SmallVec<[IVec2; 8]> {
+        match self {
+            Connectivity::Four => grid.neighbors4(pos).into_iter().collect(),
+            Connectivity::Eight => grid.neighbors8(pos),
+            Connectivity::Rows => [pos - IVec2::X, pos + IVec2::X]
+                .into_iter()
+                .filter(|n| grid.in_bounds(*n))
+                .collect(),
+        }
+    }
+}
+
+/// Breadth first search from `start` through the cells `include` accepts, returning how many
+/// steps away each reached cell is. Nothing is reached if `start` itself isn't included.
+pub fn flood_fill<G: GridLike>(
+    grid: &G,
+    start: IVec2,
+    connectivity: Connectivity,
+    include: impl Fn(IVec2, &G::Cell) -> bool,
+) -> HashMap<IVec2, usize> {
+    let included = |pos: IVec2| grid.get(pos).is_some_and(|cell| include(pos, cell));
+    let mut steps = HashMap::new();
+    if !included(start) {
+        return steps;
+    }
+    steps.insert(start, 0);
+    let mut queue = std::collections::VecDeque::from([start]);
+    while let Some(pos) = queue.pop_front() {
+        let dist = steps[&pos];
+        for neighbor in connectivity.neighbors(grid, pos) {
+            if !steps.contains_key(&neighbor) && included(neighbor) {
+                steps.insert(neighbor, dist + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    steps
+}
+
+/// A group of connected cells found by `connected_components`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    pub cells: HashSet<IVec2>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Cell edges that don't touch another cell of the region.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|pos| get_cardinal_neighbors(*pos))
+            .filter(|n| !self.cells.contains(n))
+            .count()
+    }
+
+    /// The inclusive min and max corners of the region.
+    pub fn bounds(&self) -> (IVec2, IVec2) {
+        self.cells.bounds().unwrap()
+    }
+
+    /// Straight runs of fence around the region, counted by its corners since each side
+    /// starts at exactly one.
+    pub fn sides(&self) -> usize {
+        let corners = [
+            IVec2::new(1, 1),
+            IVec2::new(1, -1),
+            IVec2::new(-1, 1),
+            IVec2::new(-1, -1),
+        ];
+        self.cells
+            .iter()
+            .map(|pos| {
+                corners
                     .iter()
-                    .map(|x| {
-                        if let Some(w) = get_weight(*x, state, extra_state) {
-                            Some((*x, w))
-                        } else {
-                            None
-                        }
+                    .filter(|corner| {
+                        let x = self.cells.contains(&(*pos + IVec2::new(corner.x, 0)));
+                        let y = self.cells.contains(&(*pos + IVec2::new(0, corner.y)));
+                        let diagonal = self.cells.contains(&(*pos + **corner));
+                        (!x && !y) || (x && y && !diagonal)
                     })
-                    .collect();
-                x
-            },
-        )
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// Every region of the grid, labelled in the order they were found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Components {
+    pub regions: Vec<Region>,
+    pub labels: HashMap<IVec2, usize>,
+}
+
+impl Components {
+    pub fn region_at(&self, pos: IVec2) -> Option<&Region> {
+        self.labels.get(&pos).map(|label| &self.regions[*label])
+    }
+}
+
+/// Splits the cells `include` accepts into connected regions. Cells are only joined to
+/// neighbors that `same_region` also accepts, so different plants or colours stay apart.
+pub fn connected_components<G: GridLike>(
+    grid: &G,
+    connectivity: Connectivity,
+    include: impl Fn(IVec2, &G::Cell) -> bool,
+    same_region: impl Fn(&G::Cell, &G::Cell) -> bool,
+) -> Components {
+    let mut components = Components::default();
+    for (pos, cell) in grid.iter() {
+        if components.labels.contains_key(&pos) || !include(pos, cell) {
+            continue;
+        }
+        let label = components.regions.len();
+        let cells: HashSet<IVec2> = flood_fill(grid, pos, connectivity, |n, other| {
+            include(n, other) && same_region(cell, other)
+        })
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect();
+        components
+            .labels
+            .extend(cells.iter().map(|pos| (*pos, label)));
+        components.regions.push(Region { label, cells });
     }
+    components
+}
+
+#[test]
+pub fn parse_options() {
+    let err = World::<u32>::parse("12\n3x4", &ParseOptions::default(), |token| {
+        Ok(token.parse::<u32>()?)
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Line 2, column 2: \"x\": invalid digit found in string"
+    );
+
+    let options = ParseOptions {
+        tokens: Tokens::Whitespace,
+        origin: IVec2::new(10, 0),
+        y_axis: YAxis::Up,
+    };
+    let world = World::<i32>::parse(
+        "1  -20\n 300 4",
+        &options,
+        |token| Ok(token.parse::<i32>()?),
+    )
+    .unwrap();
+    assert_eq!(world.world[&IVec2::new(11, 1)], -20);
+    assert_eq!(world.world[&IVec2::new(10, 0)], 300);
+
+    assert_eq!(
+        Tokens::Width(2).split("a1b2c"),
+        vec![(0, 1, "a1"), (1, 3, "b2"), (2, 5, "c")]
+    );
+    assert_eq!(
+        Tokens::Separator(",").split("7,,8"),
+        vec![(0, 1, "7"), (2, 4, "8")]
+    );
+    let sparse = World::<u32>::parse(
+        "7,,8",
+        &ParseOptions {
+            tokens: Tokens::Separator(","),
+            ..Default::default()
+        },
+        |token| Ok(token.parse::<u32>()?),
+    )
+    .unwrap();
+    assert_eq!(sparse.world.get(&IVec2::new(1, 0)), None);
+    assert_eq!(sparse.world[&IVec2::new(2, 0)], 8);
+
+    let chars: Result<World<char>, _> = "ab\ncd".try_into();
+    assert_eq!(chars.unwrap().world[&IVec2::new(1, 1)], 'd');
+
+    let (world, markers) = World::parse_markers(
+        "#S.\n.#E\nS..",
+        &ParseOptions::default(),
+        |c| c.is_ascii_uppercase(),
+        Some('.'),
+        |token| Ok(token.chars().next()),
+    )
+    .unwrap();
+    assert_eq!(markers.one('E').unwrap(), IVec2::new(2, 1));
+    assert_eq!(markers.all('S'), &[IVec2::new(1, 0), IVec2::new(0, 2)]);
+    assert!(markers.one('S').is_err());
+    assert_eq!(markers.iter().count(), 3);
+    assert_eq!(world.world[&IVec2::new(2, 1)], '.');
+    assert_eq!(world.world.len(), 9);
+}
+
+#[test]
+pub fn regions() {
+    let world = World::<char>::from_str("AAAA\nBBCD\nBBCC\nEEEC", |s| s.chars().next());
+    let components = connected_components(&world, Connectivity::Four, |_, _| true, |a, b| a == b);
+    assert_eq!(components.regions.len(), 5);
+    let stats = |c: char| {
+        let region = components
+            .regions
+            .iter()
+            .find(|r| world.world[r.cells.iter().next().unwrap()] == c)
+            .unwrap();
+        (region.area(), region.perimeter(), region.sides())
+    };
+    assert_eq!(stats('A'), (4, 10, 4));
+    assert_eq!(stats('B'), (4, 8, 4));
+    assert_eq!(stats('C'), (4, 10, 8));
+    assert_eq!(
+        components.region_at(IVec2::new(2, 1)).unwrap().bounds(),
+        (IVec2::new(2, 1), IVec2::new(3, 3))
+    );
+
+    let steps = flood_fill(&world, IVec2::ZERO, Connectivity::Eight, |_, c| *c != 'D');
+    assert_eq!(steps[&IVec2::new(3, 3)], 3);
+    assert!(!steps.contains_key(&IVec2::new(3, 1)));
+    let row = flood_fill(&world, IVec2::new(0, 1), Connectivity::Rows, |_, c| {
+        *c == 'B'
+    });
+    assert_eq!(row.len(), 2);
+}
+
+#[test]
+pub fn transforms() {
+    let world = World::<char>::from_str("ab\ncd\nef", |s| s.chars().next());
+    let text = |world: &World<char>| {
+        let (min, max) = world.bounds().unwrap();
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| world.world.get(&IVec2::new(x, y)).unwrap_or(&' '))
+                    .collect::<String>()
+            })
+            .join("\n")
+    };
+    assert_eq!(text(&world.rotate(Rotation::R90)), "eca\nfdb");
+    assert_eq!(text(&world.rotate(Rotation::R180)), "fe\ndc\nba");
+    assert_eq!(text(&world.rotate(Rotation::R270)), "bdf\nace");
+    assert_eq!(text(&world.flip(Axis::X)), "ba\ndc\nfe");
+    assert_eq!(text(&world.flip(Axis::Y)), "ef\ncd\nab");
+    assert_eq!(text(&world.transpose()), "ace\nbdf");
+    let cropped = world.crop(IVec2::new(1, 1), IVec2::new(5, 5));
+    assert_eq!(cropped.bounds(), Some((IVec2::new(1, 1), IVec2::new(1, 2))));
+    let moved = cropped.rotate(Rotation::R90);
+    assert_eq!(moved.bounds(), Some((IVec2::new(1, 1), IVec2::new(2, 1))));
+
+    let tiled = world.tile(2, 1, |c, tile| {
+        if tile.x == 0 {
+            *c
+        } else {
+            c.to_ascii_uppercase()
+        }
+    });
+    assert_eq!(text(&tiled), "abAB\ncdCD\nefEF");
+
+    let folded = tiled.fold(Axis::X, 2, |_, folded| *folded);
+    assert_eq!(text(&folded), "aB\ncD\neF");
+    let folded = folded.fold(Axis::Y, 1, |kept, _| *kept);
+    assert_eq!(text(&folded), "aB");
 }
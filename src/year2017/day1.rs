@@ -1,6 +1,6 @@
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -13,7 +13,7 @@ impl SolvePuzzle for AoC2017Day1 {
         (2017, 1)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         let chars = input
             .split("")
             .map(|x| x.parse::<u32>().ok())
@@ -48,10 +48,4 @@ impl SolvePuzzle for AoC2017Day1 {
     }
 }
 
-#[test]
-fn day1() -> Result<()> {
-    AoC2017Day1::run_tests()?;
-    let res = AoC2017Day1::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2017Day1, 2017, 1);
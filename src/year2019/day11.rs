@@ -15,8 +15,8 @@ pub fn base() {
         robot.run(&rx, &tx);
         tx.send(None);
     });
-    let mut white_panels = HashSet::new();
-    let mut painted_panels = HashSet::new();
+    // None until a panel is painted, then whether it's white.
+    let mut hull: ChunkedGrid<Option<bool>> = ChunkedGrid::new();
     let mut pos = IVec2::ZERO;
     let mut dir = Dir2::Up;
     robot_input.send(Some(1));
@@ -30,13 +30,7 @@ pub fn base() {
             None => break,
         };
 
-        if color == 0 && white_panels.contains(&pos) {
-            white_panels.remove(&pos);
-        }
-        if color == 1 {
-            white_panels.insert(pos);
-        }
-        painted_panels.insert(pos);
+        hull.set(pos, Some(color == 1));
 
         dir = if turn == 0 {
             dir.turn_left()
@@ -47,14 +41,27 @@ pub fn base() {
         let diff: IVec2 = dir.into();
         pos = pos + diff;
 
-        if white_panels.contains(&pos) {
+        if *hull.get(pos) == Some(true) {
             robot_input.send(Some(1));
         } else {
             robot_input.send(Some(0));
         }
     }
 
-    dbg!(painted_panels.len());
+    assert_eq!(
+        hull.iter().filter(|(_, panel)| panel.is_some()).count(),
+        249
+    );
+    let white_panels: HashSet<IVec2> = hull
+        .iter()
+        .filter(|(_, panel)| **panel == Some(true))
+        .map(|(pos, _)| pos)
+        .collect();
 
-    pretty_print_set(&white_panels, &|_| "#".to_string(), 1)
+    // The robot treats up as +y, OCR expects rows to grow downwards.
+    let flipped: HashSet<IVec2> = white_panels
+        .iter()
+        .map(|pos| IVec2::new(pos.x, -pos.y))
+        .collect();
+    assert_eq!(ocr_set(&flipped).ok(), Some("UZAEKBLP".to_owned()));
 }
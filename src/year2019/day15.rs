@@ -191,29 +191,10 @@ pub fn day_15() {
     );
     dbg!(x);
 
-    let mut mins = -1;
-    let mut updates = HashSet::new();
-    updates.insert(oxygen_start);
-    while !updates.is_empty() {
-        mins += 1;
-        let mut next = HashSet::new();
-
-        for pos in updates {
-            state.world.insert(pos, Tile::Target);
-            for neigh in get_cardinal_neighbors(pos) {
-                if let Some(tile) = state.world.get(&neigh) {
-                    match tile {
-                        Tile::Empty => {
-                            next.insert(neigh);
-                        }
-                        _ => (),
-                    }
-                }
-            }
-        }
-        updates = next;
-    }
-
+    let oxygen = flood_fill(&state, oxygen_start, Connectivity::Four, |_, tile| {
+        *tile != Tile::Wall
+    });
+    let mins = oxygen.values().max().unwrap();
     dbg!(mins);
     input.send(None);
 }
@@ -3,8 +3,8 @@ use std::fmt::Display;
 use crate::utils::{
     aocdata::{Part, TestCase},
     get_cardinal_neighbors,
-    puzzle::{PuzzleFns, SolvePuzzle},
-    Dir2, World,
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle, Verbosity},
+    Dir2, GridLike, ParseOptions, World,
 };
 use anyhow::{anyhow, Result};
 use glam::IVec2;
@@ -79,7 +79,7 @@ impl SolvePuzzle for AoC2019Day17 {
         (2019, 17)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let mut numbers = IntcodeMachine::parse_str(input).unwrap();
         let (_input, output) = IntcodeMachine::spawn(numbers.clone());
         numbers[0] = 2;
@@ -91,22 +91,23 @@ impl SolvePuzzle for AoC2019Day17 {
             .map(|x| x as u8)
             .collect_vec();
         let string = String::from_utf8(view).unwrap();
-        let mut robot_start_dir = Dir2::Up;
-        let mut robot_start_pos = IVec2::ZERO;
-        let mut world =
-            World::<CamOut>::from_str(
-                &string,
-                |x| CamOut::from_u8(x.chars().nth(0).unwrap() as u8),
-            );
+        let (mut world, markers) = World::<CamOut>::parse_markers(
+            &string,
+            &ParseOptions::default(),
+            |c| "^v<>".contains(c),
+            None,
+            |x| Ok(CamOut::from_u8(x.chars().next().unwrap() as u8)),
+        )?;
+        let (_, robot_start_pos) = markers
+            .iter()
+            .next()
+            .ok_or_else(|| anyhow!("No robot in the camera view"))?;
+        let robot_start_dir = world.world[&robot_start_pos].is_robot().unwrap();
 
         let sum: i32 = world
             .world
             .iter()
             .map(|(pos, cam)| {
-                if let Some(dir) = cam.is_robot() {
-                    robot_start_pos = *pos;
-                    robot_start_dir = dir;
-                }
                 if cam.is_scaffolding() {
                     let neighbors = get_cardinal_neighbors(*pos);
                     let intersection = neighbors
@@ -126,7 +127,7 @@ impl SolvePuzzle for AoC2019Day17 {
                         .count()
                         > 2;
                     if intersection {
-                        dbg!(pos);
+                        ctx.debug(format_args!("Intersection at {pos}"));
                         pos.x * pos.y
                     } else {
                         0
@@ -137,7 +138,9 @@ impl SolvePuzzle for AoC2019Day17 {
             })
             .sum();
 
-        world.pretty_print(false);
+        if ctx.enabled(Verbosity::Debug) {
+            world.pretty_print(false);
+        }
 
         let next_dir = |pos: IVec2, visited: &HashSet<IVec2>| {
             let neighbors = get_cardinal_neighbors(pos);
@@ -184,18 +187,19 @@ impl SolvePuzzle for AoC2019Day17 {
         let mut visited = HashSet::new();
         let mut dir = robot_start_dir;
         let mut pos = robot_start_pos;
+        let mut path = String::new();
         while let Some(next_dir) = next_dir(pos, &visited) {
             let cmd = get_cmd(dir, next_dir);
             let next_pos = next_pos(pos, next_dir);
             let dist = (next_pos - pos).abs().max_element();
-            print!("{cmd}{dist}");
+            path.push_str(&format!("{cmd}{dist}"));
             let next_offset: IVec2 = next_dir.into();
             visited.insert(next_pos - next_offset);
             visited.insert(next_pos);
             pos = next_pos;
             dir = next_dir;
         }
-        println!("");
+        ctx.info(format_args!("Path to compress: {path}"));
         let (input, output) = IntcodeMachine::spawn(numbers.clone());
         IntcodeMachine::send_str(&input, "A,A,B,C,B,C,B,C,B,A\n");
         IntcodeMachine::send_str(&input, "L,10,L,8,R,8,L,8,R,6\n");
@@ -216,13 +220,7 @@ impl SolvePuzzle for AoC2019Day17 {
     }
 }
 
-#[test]
-fn day1() -> Result<()> {
-    AoC2019Day17::run_tests()?;
-    let res = AoC2019Day17::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2019Day17, 2019, 17);
 
 /*
 L10L8R8L8R6L10L8R8L8R6R6R8R8R6R6L8L10R6R8R8R6R6L8L10R6R8R8R6R6L8L10R6R8R8L10L8R8L8R6
@@ -6,8 +6,8 @@ use std::{
 use crate::utils::{
     aocdata::{Part, TestCase},
     djikstra, get_cardinal_neighbors,
-    puzzle::{PuzzleFns, SolvePuzzle},
-    World,
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle, Verbosity},
+    GridLike, ParseOptions, World,
 };
 use anyhow::{anyhow, Error, Result};
 use glam::IVec2;
@@ -121,16 +121,19 @@ impl SolvePuzzle for AoC2019Day18 {
     fn puzzle_year_day() -> (i32, u32) {
         (2019, 18)
     }
-    fn solve(input: &str) -> Result<Self::Output> {
-        let mut world: World<Tile> = input.try_into()?;
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
+        let (mut world, markers) = World::parse_markers(
+            input,
+            &ParseOptions::default(),
+            |c| c == '@',
+            Some(Tile::Empty),
+            |token| Tile::try_from(token.chars().next().unwrap()),
+        )?;
 
-        world.pretty_print(false);
-        let pos = *world
-            .world
-            .iter()
-            .find(|(_, t)| **t == Tile::Player)
-            .unwrap()
-            .0;
+        if ctx.enabled(Verbosity::Debug) {
+            world.pretty_print(false);
+        }
+        let pos = markers.one('@')?;
         let start = SearchState {
             pos,
             keys: TinyVec::new(),
@@ -143,7 +146,7 @@ impl SolvePuzzle for AoC2019Day18 {
                 _ => 0,
             })
             .sum();
-        dbg!(key_count);
+        ctx.debug(format_args!("{key_count} keys"));
         let cost = djikstra(
             start,
             0,
@@ -207,7 +210,9 @@ impl SolvePuzzle for AoC2019Day18 {
         b_world
             .world
             .insert(IVec2::new(mid_x, mid_y + 1), Tile::Wall);
-        b_world.pretty_print(false);
+        if ctx.enabled(Verbosity::Debug) {
+            b_world.pretty_print(false);
+        }
 
         let start2 = SearchStateB {
             pos: [
@@ -330,10 +335,4 @@ impl SolvePuzzle for AoC2019Day18 {
     }
 }
 
-#[test]
-fn test() -> Result<()> {
-    AoC2019Day18::run_tests()?;
-    let res = AoC2019Day18::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2019Day18, 2019, 18);
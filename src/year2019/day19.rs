@@ -2,8 +2,8 @@ use std::collections::VecDeque;
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
-    World,
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle, Verbosity},
+    GridLike, World,
 };
 use anyhow::Result;
 use glam::IVec2;
@@ -21,7 +21,7 @@ impl SolvePuzzle for AoC2019Day19 {
         (2019, 19)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let mut numbers = IntcodeMachine::parse_str(input).unwrap();
         let in_emitter = |x, y| {
             if x < 0 || y < 0 {
@@ -43,39 +43,43 @@ impl SolvePuzzle for AoC2019Day19 {
         buffer.push_back((3, 5));
         while let Some((x, y)) = buffer.pop_front() {
             if in_emitter(x, y) == 0 {
-                dbg!(x, y);
+                ctx.debug(format_args!("({x}, {y}) is outside the beam"));
                 continue;
             }
             if in_emitter(x + d, y - d) == 1 {
                 res = (x, y);
-                dbg!(res);
+                ctx.debug(format_args!("Square fits at {res:?}"));
                 break;
             }
 
             if in_emitter(x, y + 1) == 1 {
-                dbg!((x, y + 1));
+                ctx.debug(format_args!("Following the beam to {:?}", (x, y + 1)));
                 buffer.push_back((x, y + 1));
             } else if in_emitter(x + 1, y + 1) == 1 {
-                dbg!((x + 1, y + 1));
+                ctx.debug(format_args!("Following the beam to {:?}", (x + 1, y + 1)));
                 buffer.push_back((x + 1, y + 1));
             } else if in_emitter(x + 1, y) == 1 {
-                dbg!((x + 1, y));
+                ctx.debug(format_args!("Following the beam to {:?}", (x + 1, y)));
                 buffer.push_back((x + 1, y));
             }
         }
         let (x, y) = res;
-        dbg!(x, y);
-        dbg!(x, y - d);
+        ctx.info(format_args!(
+            "Bottom left ({x}, {y}), top left ({x}, {})",
+            y - d
+        ));
 
-        let mut world: World<char> = World {
-            world: HashMap::new(),
-        };
+        if ctx.enabled(Verbosity::Debug) {
+            let mut world: World<char> = World {
+                world: HashMap::new(),
+            };
 
-        for (x, y) in (0..50i64).flat_map(|x| (0..50i64).into_iter().map(move |y| (x, y))) {
-            let c = if in_emitter(x, y) == 1 { '#' } else { '.' };
-            world.world.insert(IVec2::new(x as i32, y as i32), c);
+            for (x, y) in (0..50i64).flat_map(|x| (0..50i64).into_iter().map(move |y| (x, y))) {
+                let c = if in_emitter(x, y) == 1 { '#' } else { '.' };
+                world.world.insert(IVec2::new(x as i32, y as i32), c);
+            }
+            world.pretty_print(false);
         }
-        world.pretty_print(false);
 
         // let (input, output) = IntcodeMachine::spawn(numbers.clone());
         // input.send(Some(0));
@@ -96,10 +100,4 @@ impl SolvePuzzle for AoC2019Day19 {
     }
 }
 
-#[test]
-fn day1() -> Result<()> {
-    AoC2019Day19::run_tests()?;
-    let res = AoC2019Day19::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2019Day19, 2019, 19);
@@ -1,4 +1,5 @@
 use crate::utils::*;
+use hashbrown::HashSet;
 use itertools::Itertools;
 use rayon::iter::*;
 use std::str;
@@ -64,6 +65,12 @@ pub fn base() {
                         .join("");
                     println!("{}", text);
                 }
+                let lit: HashSet<IVec2> = (0..HEIGHT)
+                    .cartesian_product(0..WIDTH)
+                    .filter(|(y, x)| pixels[*y][*x] == 1)
+                    .map(|(y, x)| IVec2::new(x as i32, y as i32))
+                    .collect();
+                assert_eq!(ocr_set(&lit).ok(), Some("LBRCE".to_owned()));
             }
         }
     }
@@ -1,5 +1,5 @@
 use crate::utils::aocdata::{Part, TestCase};
-use crate::utils::puzzle::{PuzzleFns, SolvePuzzle};
+use crate::utils::puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle};
 use crate::utils::read_lines;
 use anyhow::Result;
 use itertools::Itertools;
@@ -59,7 +59,7 @@ impl SolvePuzzle for Year2021Day1 {
         (2021, 1)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         Ok((count_lines(input)?, None))
     }
 
@@ -81,13 +81,7 @@ impl SolvePuzzle for Year2021Day1 {
     }
 }
 
-#[test]
-pub fn submit_year_2021_day_1() -> Result<()> {
-    let _tests = Year2021Day1::run_tests()?;
-    let res = Year2021Day1::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(Year2021Day1, 2021, 1);
 
 pub fn count_lines(input: &str) -> Result<i32> {
     Ok(input
@@ -1,71 +1,87 @@
-use std::collections::{HashMap, HashSet};
-
+use crate::utils::aocdata::{Part, TestCase};
+use crate::utils::puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle};
 use crate::utils::*;
+use anyhow::{anyhow, Result};
+use hashbrown::HashMap;
 
-pub fn fold_paper(paper: &HashSet<IVec2>, axis: usize, val: i32) -> HashSet<IVec2> {
-    paper.iter().fold(HashSet::new(), |mut acc, elem| {
-        let new_elem = if elem[axis] < val {
-            *elem
-        } else {
-            let mut v = *elem;
-            v[axis] = (2 * val) - v[axis];
-            v
-        };
-        acc.insert(new_elem);
-        acc
-    })
-}
+pub type Fold = (Axis, i32);
 
-pub fn pretty_print(paper: &HashSet<IVec2>) {
-    let max_x = paper.iter().map(|x| x.x).max().unwrap();
-    let max_y = paper.iter().map(|x| x.x).max().unwrap();
-    for y in 0..max_y {
-        let mut row = vec![];
-        for x in 0..=max_x {
-            let pos = IVec2::new(x, y);
-            if paper.contains(&pos) {
-                row.push("#");
-            } else {
-                row.push(" ")
-            }
+pub fn parse_paper(input: &str) -> Result<(World<char>, Vec<Fold>)> {
+    let mut paper = HashMap::new();
+    let mut folds = vec![];
+    for line in input.lines() {
+        if let Some((x, y)) = line.split_once(',') {
+            paper.insert(IVec2::new(x.parse()?, y.parse()?), '#');
+        } else if let Some(fold) = line.strip_prefix("fold along ") {
+            let (axis, val) = fold
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid fold {line}"))?;
+            let axis = match axis {
+                "x" => Axis::X,
+                _ => Axis::Y,
+            };
+            folds.push((axis, val.parse()?));
         }
-        println!("{}", row.join(" "));
     }
+    Ok((World { world: paper }, folds))
 }
 
-#[test]
-pub fn day_13() {
-    if let Ok(lines) = read_lines("./src/year2021/data/day13input.txt") {
-        // Consumes the iterator, returns an (Optional) String
+pub struct AoC2021Day13;
 
-        let mut paper = HashSet::new();
-        let mut folds = vec![];
-        for (_line_num, line) in lines.enumerate() {
-            if let Ok(contents) = line {
-                let pair: Vec<_> = contents.split(",").collect();
-                if pair.len() > 1 {
-                    let ints: Vec<i32> = pair.iter().map(|s| s.parse().unwrap()).collect();
-                    paper.insert(IVec2::new(ints[0], ints[1]));
-                } else {
-                    let fold_line: Vec<_> = contents.split_whitespace().collect();
-                    if fold_line.len() == 3 {
-                        let fold: Vec<_> = fold_line[2].split("=").collect();
-                        let axis: usize = match fold[0] {
-                            "x" => 0,
-                            _ => 1,
-                        };
-                        let val: i32 = fold[1].parse().unwrap();
-                        folds.push((axis, val));
-                    }
-                }
-            }
-        }
-        let (axis, val) = folds[0];
-        let first_fold = fold_paper(&paper, axis, val);
-        dbg!(first_fold.len());
-        for (axis, val) in folds {
-            paper = fold_paper(&paper, axis, val)
+impl SolvePuzzle for AoC2021Day13 {
+    type Output = (usize, Result<String>);
+    fn puzzle_year_day() -> (i32, u32) {
+        (2021, 13)
+    }
+
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
+        let (mut paper, folds) = parse_paper(input)?;
+        let (axis, line) = *folds.first().ok_or_else(|| anyhow!("No folds"))?;
+        let first_fold = paper.fold(axis, line, |dot, _| *dot).world.len();
+        for (axis, line) in folds {
+            paper = paper.fold(axis, line, |dot, _| *dot);
         }
-        pretty_print(&paper);
+        // The example folds into a square rather than letters, its part B error is only logged.
+        Ok((first_fold, paper.ocr(|_| true)))
     }
+
+    fn test_cases() -> Vec<TestCase> {
+        vec![TestCase::new(
+            Part::A,
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5",
+            17,
+        )]
+    }
+}
+
+puzzle_tests!(AoC2021Day13, 2021, 13);
+
+#[test]
+pub fn day_13() -> Result<()> {
+    let input = std::fs::read_to_string("./src/year2021/data/day13input.txt")?;
+    let ctx = PuzzleContext::default();
+    let (part_a, part_b) = AoC2021Day13::solve(&input, &ctx)?;
+    assert_eq!(part_a, 695);
+    assert_eq!(part_b?, "GJZGLUPJ");
+    Ok(())
 }
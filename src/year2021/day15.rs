@@ -29,36 +29,38 @@ pub fn ridged_noise() {
     img.save("./src/year2021/viz/day15/aaa.png");
 
     let mut map = World { world };
-    find_path(&mut map, 1);
+    find_path(&mut map, 1, false);
 }
 
 pub fn day_15() {
-    if let Some(mut map) = World::<usize>::from_file("./src/year2021/data/day15input.txt", |s| {
+    if let Some(map) = World::<usize>::from_file("./src/year2021/data/day15input.txt", |s| {
         let x: usize = s.parse().unwrap();
         x
     }) {
-        let max_x = map.max_x() + 1;
-        let max_y = map.max_y() + 1;
-        let points: Vec<_> = map.world.iter().map(|(&x, &y)| (x, y)).collect();
-        for (pt, weight) in points {
-            let weight = weight - 1;
-            for x in 0..5 {
-                for y in 0..5 {
-                    if x == 0 && y == 0 {
-                        continue;
-                    }
-                    let pos = IVec2::new((x * max_x) + pt.x, (y * max_y) + pt.y);
+        let mut map = map.tile(5, 5, |weight, tile| {
+            ((weight - 1 + tile.x as usize + tile.y as usize) % 9) + 1
+        });
+        find_path(&mut map, 3, false);
+    }
+}
 
-                    map.world
-                        .insert(pos, ((weight + x as usize + y as usize) % 9) + 1);
-                }
-            }
-        }
-        find_path(&mut map, 3);
+/// White at the start fading to black for the furthest nodes reached so far.
+fn distance_shade(distances: &HashMap<IVec2, usize>) -> impl Fn(IVec2, &usize) -> Rgba<u8> + '_ {
+    let max = (*distances
+        .values()
+        .filter(|&&x| x < usize::MAX)
+        .max()
+        .unwrap_or(&1)) as f32
+        * 1.1;
+    move |pos, _| {
+        let distance = (*distances.get(&pos).unwrap_or(&0) as f32).min(max);
+        let brightness = (255. - distance / max * 255.) as u8;
+        Rgba([brightness, brightness, brightness, 255])
     }
 }
 
-pub fn find_path(map: &mut World<usize>, px_per_node: u32) {
+/// With `animate` the search is also saved as a GIF, one frame per batch of nodes.
+pub fn find_path(map: &mut World<usize>, px_per_node: u32, animate: bool) {
     let mut completed = HashSet::new();
     let mut heap: BinaryHeap<MinWeight<IVec2>> = BinaryHeap::new();
     let mut distances = HashMap::new();
@@ -84,36 +86,15 @@ pub fn find_path(map: &mut World<usize>, px_per_node: u32) {
     let cost_scale = *map.world.values().min().unwrap();
     dbg!(cost_scale);
 
-    let image_width = (map.max_x() + 1) as u32;
-    let image_height = (map.max_y() + 1) as u32;
     let mut nodes_in_frame = 0;
-    let nodes_per_frame = ((image_width * image_height) / (24 * 20)).max(1);
-    let mut frame_num = 0;
-
-    let mut write_frame = |distances: &HashMap<IVec2, usize>| {
-        let max = (*distances
-            .values()
-            .filter(|&&x| x < usize::MAX)
-            .max()
-            .unwrap_or(&1)) as f32
-            * 1.1;
-        let img = ImageBuffer::from_fn(
-            image_width * px_per_node,
-            image_height * px_per_node,
-            |x, y| {
-                let pos = IVec2::new((x / px_per_node) as i32, (y / px_per_node) as i32);
-                let distance = *distances.get(&pos).unwrap_or(&0);
-                let distance = (distance as f32).min(max);
-                let ratio = (distance as f32) / (max as f32);
-                let brightness = ratio * 255.;
-
-                image::Luma([(255. - brightness) as u8])
-            },
-        );
-        img.save(format!("./src/year2021/viz/day15/map{:03}.png", frame_num))
-            .unwrap();
-        frame_num += 1;
-    };
+    let nodes_per_frame = (map.world.len() as u32 / (24 * 20)).max(1);
+    let mut recorder = FrameRecorder::new(
+        RenderOptions {
+            scale: px_per_node,
+            ..Default::default()
+        },
+        std::time::Duration::from_millis(40),
+    );
 
     while let Some(pos) = heap.pop() {
         if !completed.contains(&pos.dat) {
@@ -147,13 +128,20 @@ pub fn find_path(map: &mut World<usize>, px_per_node: u32) {
             }
 
             nodes_in_frame += 1;
-            if nodes_in_frame == nodes_per_frame {
-                // write_frame(&distances);
+            if animate && nodes_in_frame == nodes_per_frame {
+                recorder.record(map, distance_shade(&distances));
                 nodes_in_frame = 0;
             }
         }
     }
-    write_frame(&distances);
+    let image = render(map, &recorder.options, distance_shade(&distances));
+    save_png(&image, "./src/year2021/viz/day15/map.png").unwrap();
+    if animate {
+        recorder.record(map, distance_shade(&distances));
+        recorder
+            .save_gif("./src/year2021/viz/day15/search.gif")
+            .unwrap();
+    }
     //visited_world.pretty_print();
     dbg!((map.max_x() + 1) * (map.max_y() + 1));
     dbg!(heap.len());
@@ -1,138 +1,63 @@
 use crate::utils::*;
-use hashbrown::{HashMap, HashSet};
+use anyhow::{anyhow, Result};
 use image::ImageBuffer;
 use itertools::Itertools;
-use rayon::iter::*;
 
+/// An image that stretches forever, everything outside the board is the automaton's background.
 pub struct InfiniteImage {
-    pub pxs: HashMap<IVec2, u8>,
-    pub default: u8,
+    pub pxs: Automaton<u8>,
 }
 
 impl InfiniteImage {
-    pub fn pretty_print(&self) {
-        let max_y = self.max_y();
-        let max_x = self.max_x();
-        let min_x = self.min_x().min(0);
-        let min_y = self.min_y().min(0);
-        for y in min_y..=max_y {
-            let mut row = vec![];
-            for x in min_x..=max_x {
-                let pos = IVec2::new(x, y);
-                if *self.pxs.get(&pos).unwrap_or(&self.default) == 1 {
-                    row.push("#")
-                } else {
-                    row.push(".")
-                }
-            }
-            println!("{}", row.iter().join(""));
+    pub fn new(board: Grid<u8>) -> Self {
+        InfiniteImage {
+            pxs: Automaton::new(board, 0, Edges::Infinite)
+                .with_neighborhood(&get_neighbors_and_pos(IVec2::ZERO)),
         }
     }
 
-    pub fn max_x(&self) -> i32 {
-        self.pxs.iter().map(|pos| pos.0.x).max().unwrap()
-    }
-    pub fn max_y(&self) -> i32 {
-        self.pxs.iter().map(|pos| pos.0.y).max().unwrap()
+    pub fn pretty_print(&self) {
+        self.pretty_print_custom(|px, _| if *px == 1 { "#" } else { "." }.to_string(), false);
     }
 
-    pub fn min_x(&self) -> i32 {
-        self.pxs.iter().map(|pos| pos.0.x).min().unwrap()
-    }
-    pub fn min_y(&self) -> i32 {
-        self.pxs.iter().map(|pos| pos.0.y).min().unwrap()
+    /// The 3x3 neighborhood read as a binary number picks the new color from `data`.
+    pub fn enhance(&mut self, data: &[u8]) {
+        let rule = |_: &u8, neighborhood: &[u8]| {
+            data[neighborhood
+                .iter()
+                .fold(0, |num, px| (num << 1) | *px as usize)]
+        };
+        self.pxs.step(&rule);
     }
+}
 
-    pub fn enhance(&self, data: &[u8]) -> InfiniteImage {
-        let blinks = data[0] != data[data.len() - 1] && data[0] > 0;
-        let min_px = IVec2::new(i32::MAX, i32::MAX);
-        let max_px = IVec2::new(i32::MIN, i32::MIN);
-        let recolor = |px: IVec2| {
-            let mut shift = 8;
-            let mut num = 0usize;
-            for pos in get_neighbors_and_pos(px) {
-                let color = *self.pxs.get(&pos).unwrap_or(&self.default);
-                num = num | ((color as usize) << shift);
-                shift -= 1;
-            }
-            let color = data[num];
-            color
-        };
-        let (min_px, max_px) = self
-            .pxs
-            .par_keys()
-            .fold(
-                || (min_px, max_px),
-                |acc, &px| (acc.0.min(px), acc.1.max(px)),
-            )
-            .reduce(|| (min_px, max_px), |x, y| (x.0.min(y.0), x.1.max(y.1)));
-        let mut img: HashMap<_, _> = self
-            .pxs
-            .par_keys()
-            .map(|&source_px| (source_px, recolor(source_px)))
-            .collect();
+impl GridLike for InfiniteImage {
+    type Cell = u8;
 
-        let default = if blinks {
-            for x in min_px.x - 1..=max_px.x + 1 {
-                let px = IVec2::new(x, min_px.y - 1);
-                img.insert(px, recolor(px));
-                let px = IVec2::new(x, max_px.y + 1);
-                img.insert(px, recolor(px));
-            }
-            for y in min_px.y - 1..=max_px.y + 1 {
-                let px = IVec2::new(min_px.x - 1, y);
-                img.insert(px, recolor(px));
-                let px = IVec2::new(max_px.x + 1, y);
-                img.insert(px, recolor(px));
-            }
+    fn get(&self, pos: IVec2) -> Option<&u8> {
+        Some(self.pxs.get(pos))
+    }
 
-            (self.default + 1) % 2
-        } else {
-            self.default
-        };
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        self.pxs.board.bounds()
+    }
 
-        InfiniteImage { pxs: img, default }
+    fn iter(&self) -> impl Iterator<Item = (IVec2, &u8)> + '_ {
+        self.pxs.board.iter()
     }
 }
 
-pub fn day20() {
-    if let Ok(lines) = read_lines("./src/year2021/data/day20input.txt") {
-        // Consumes the iterator, returns an (Optional) String
-        let mut img: HashMap<IVec2, u8> = HashMap::new();
-        let mut data = vec![];
-        for (y, line) in lines.enumerate() {
-            if let Ok(contents) = line {
-                if y == 0 {
-                    data = contents
-                        .split("")
-                        .filter(|s| s.len() > 0)
-                        .map(|s| match s {
-                            "#" => 1u8,
-                            _ => 0u8,
-                        })
-                        .collect();
-                }
-                if y > 1 {
-                    let y = y - 2;
-                    for (x, color) in contents
-                        .split("")
-                        .filter(|s| s.len() > 0)
-                        .map(|s| match s {
-                            "#" => 1u8,
-                            _ => 0u8,
-                        })
-                        .enumerate()
-                    {
-                        img.insert(IVec2::new(x as i32, y as i32), color);
-                    }
-                }
-            }
-        }
+fn parse_image(input: &str) -> Result<(Vec<u8>, InfiniteImage)> {
+    let color = |line: &str| line.chars().map(|c| (c == '#') as u8).collect_vec();
+    let mut lines = input.lines();
+    let data = color(lines.next().ok_or_else(|| anyhow!("No enhancement data"))?);
+    let board = Grid::from_rows(lines.skip(1).map(color).collect())?;
+    Ok((data, InfiniteImage::new(board)))
+}
 
-        let mut pic = InfiniteImage {
-            pxs: img,
-            default: 0,
-        };
+pub fn day20() {
+    if let Ok(input) = std::fs::read_to_string("./src/year2021/data/day20input.txt") {
+        let (data, mut pic) = parse_image(&input).unwrap();
         let mut frame_num = 0;
 
         let image_width = 500;
@@ -149,7 +74,7 @@ pub fn day20() {
                         (x / px_per_node) as i32 - offset,
                         (y / px_per_node) as i32 - offset,
                     );
-                    let color = *pic.pxs.get(&pos).unwrap_or(&0);
+                    let color = *pic.pxs.get(pos);
                     let brightness = if color == 0 { 255u8 } else { 0u8 };
                     image::Luma([brightness])
                 },
@@ -162,9 +87,22 @@ pub fn day20() {
         write_image(&pic);
 
         for _ in 0..150 {
-            pic = pic.enhance(&data);
+            pic.enhance(&data);
             write_image(&pic);
         }
-        dbg!(pic.pxs.values().filter(|&&x| x > 0).count());
+        dbg!(pic.iter().filter(|(_, &x)| x > 0).count());
+    }
+}
+
+#[test]
+pub fn enhance_image() {
+    if let Ok(input) = std::fs::read_to_string("./src/year2021/data/day20input.txt") {
+        let (data, mut pic) = parse_image(&input).unwrap();
+        let mut lit_after = |steps| {
+            (0..steps).for_each(|_| pic.enhance(&data));
+            pic.iter().filter(|(_, &x)| x > 0).count()
+        };
+        assert_eq!(lit_after(2), 5361);
+        assert_eq!(lit_after(48), 16826);
     }
 }
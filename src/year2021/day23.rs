@@ -1,3 +1,4 @@
+use crate::utils::puzzle::PuzzleContext;
 use crate::utils::*;
 use core::cmp::Ordering;
 use hashbrown::{HashMap, HashSet};
@@ -481,7 +482,7 @@ pub fn day23_example() {
 
     // state.pretty_print();
 
-    // find_path(&mut state);
+    // find_path(&mut state, &PuzzleContext::default());
 }
 
 pub fn day23() {
@@ -509,27 +510,26 @@ pub fn day23() {
 
     state.pretty_print();
 
-    find_path(&mut state);
+    let ctx = PuzzleContext {
+        show_progress: true,
+        ..Default::default()
+    };
+    find_path(&mut state, &ctx);
 }
 
-pub fn find_path(state: &mut State) {
+pub fn find_path(state: &mut State, ctx: &PuzzleContext) {
     let mut completed = HashSet::new();
     let mut heap: BinaryHeap<WeightedPos> = BinaryHeap::new();
     heap.push(WeightedPos::new(0, 0, state.clone()));
 
-    let mut visit_num = 0;
+    let progress = ctx.progress("States visited", None);
     let _diagnostics = false;
     let mut cost = 0;
 
     while let Some(pos) = heap.pop() {
         if !completed.contains(&pos.state) {
             completed.insert(pos.state.clone());
-
-            visit_num += 1;
-            if visit_num % 50000 == 0 {
-                pos.state.pretty_print();
-                dbg!(visit_num, pos.cost);
-            }
+            progress.inc(1);
 
             if pos.state.done() {
                 cost = pos.cost;
@@ -1,3 +1,5 @@
+use crate::utils::progress::Progress;
+use crate::utils::puzzle::PuzzleContext;
 use crate::utils::*;
 use core::cmp::Ordering;
 use hashbrown::{HashMap, HashSet};
@@ -40,16 +42,23 @@ pub fn test_fns() {
     }
 }
 
-pub fn find_highest_passing() {
+pub fn find_highest_passing(ctx: &PuzzleContext) {
     //let z = test_ith_digit(1, 0, 0);
-    let x = test_z(0, 0);
+    let progress = ctx.progress("Two digit prefixes", Some(81));
+    let x = test_z(0, 0, &progress);
     dbg!(x);
 }
 
-pub fn test_z(i: usize, z: i64) -> Option<i64> {
-    if i < 3 {
-        println!("{}{}", format!("{:width$}", "", width = i), i);
+pub fn test_z(i: usize, z: i64, progress: &Progress) -> Option<i64> {
+    let res = search_digits(i, z, progress);
+    // Each search from depth 2 covers one of the 81 two digit prefixes.
+    if i == 2 {
+        progress.inc(1);
     }
+    res
+}
+
+fn search_digits(i: usize, z: i64, progress: &Progress) -> Option<i64> {
     const DIGITS: [i64; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
     if i == 4 || i == 5 {
         let x: Vec<_> = DIGITS
@@ -67,7 +76,7 @@ pub fn test_z(i: usize, z: i64) -> Option<i64> {
                             return Some(digit);
                         }
                     } else {
-                        if let Some(num) = test_z(i + 1, next_z) {
+                        if let Some(num) = test_z(i + 1, next_z, progress) {
                             let val = 10i64.pow(13 - i as u32) * digit;
                             return Some(num + val);
                         }
@@ -94,7 +103,7 @@ pub fn test_z(i: usize, z: i64) -> Option<i64> {
                     return Some(digit);
                 }
             } else {
-                if let Some(num) = test_z(i + 1, next_z) {
+                if let Some(num) = test_z(i + 1, next_z, progress) {
                     let val = 10i64.pow(13 - i as u32) * digit;
                     return Some(num + val);
                 }
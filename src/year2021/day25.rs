@@ -1,75 +1,23 @@
 use crate::utils::*;
-use core::cmp::Ordering;
-use hashbrown::{HashMap, HashSet};
-use std::collections::BinaryHeap;
-
-#[derive(Copy, Clone, Debug)]
-pub struct Cucumber {
-    pub dir: Dir2,
-}
 
 #[test]
 pub fn day_25() {
-    if let Some(mut map) = World::<Cucumber>::from_file("./src/year2021/data/day25input.txt", |s| {
-        let cuc = match s {
-            "v" => Some(Cucumber { dir: Dir2::Down }),
-            ">" => Some(Cucumber { dir: Dir2::Right }),
-            _ => None,
+    if let Ok(input) = std::fs::read_to_string("./src/year2021/data/day25input.txt") {
+        let board = Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect());
+        let mut map = Automaton::new(board.unwrap(), '.', Edges::Wrap)
+            .with_neighborhood(&get_cardinal_neighbors(IVec2::ZERO));
+        // Neighbors are left, right, up and down. The east herd moves before the south herd.
+        let east = |cell: &char, n: &[char]| match cell {
+            '.' if n[0] == '>' => '>',
+            '>' if n[1] == '.' => '.',
+            _ => *cell,
         };
-        cuc
-    }) {
-        let max_x = map.max_x() + 1;
-        let max_y = map.max_y() + 1;
-        let mut i = 0;
-        let mut done = false;
-        while !done {
-            i += 1;
-            done = true;
-            let mut new_map = World {
-                world: HashMap::new(),
-            };
-
-            for (pos, cuc) in map.world.iter().filter(|(_, cuc)| cuc.dir == Dir2::Right) {
-                let new_x = (pos.x + 1) % max_x;
-                let new_pos = IVec2::new(new_x, pos.y);
-                if !map.world.contains_key(&new_pos) {
-                    done = false;
-                    new_map.world.insert(new_pos, *cuc);
-                } else {
-                    new_map.world.insert(*pos, *cuc);
-                }
-            }
-
-            for (pos, cuc) in map.world.iter().filter(|(_, cuc)| cuc.dir == Dir2::Down) {
-                let new_y = (pos.y + 1) % max_y;
-                let new_pos = IVec2::new(pos.x, new_y);
-                if let Some(other_cuc) = map.world.get(&new_pos) {
-                    if other_cuc.dir == Dir2::Down {
-                        new_map.world.insert(*pos, *cuc);
-                        continue;
-                    }
-                }
-                if let Some(other_cuc) = new_map.world.get(&new_pos) {
-                    if other_cuc.dir == Dir2::Right {
-                        new_map.world.insert(*pos, *cuc);
-                        continue;
-                    }
-                }
-                done = false;
-                new_map.world.insert(new_pos, *cuc);
-            }
-            map = new_map;
-            dbg!(i);
-            // map.pretty_print(&|x| {
-            //         match x.dir {
-            //             Dir2::Right => ">",
-            //             Dir2::Down => "v",
-            //             _ => " "
-            //         }.to_string()
-            //     }, false);
-            // if i > 3 {
-            //     break
-            // }
-        }
+        let south = |cell: &char, n: &[char]| match cell {
+            '.' if n[2] == 'v' => 'v',
+            'v' if n[3] == '.' => '.',
+            _ => *cell,
+        };
+        let i = map.run_until_stable(usize::MAX, |map| map.step_ordered(&[&east, &south]));
+        assert_eq!(i, Some(579));
     }
 }
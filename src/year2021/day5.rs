@@ -5,7 +5,7 @@ use crate::utils::*;
 #[test]
 pub fn count_intersections() {
     if let Ok(lines) = read_lines("./src/year2021/data/day5input.txt") {
-        let mut world: VecWorld<bool> = VecWorld::new();
+        let mut world: ChunkedGrid<bool> = ChunkedGrid::new();
         let mut intersections: HashSet<IVec2> = HashSet::new();
         let _skip_diagonal = true;
         // Consumes the iterator, returns an (Optional) String
@@ -1,7 +1,10 @@
 use std::collections::{BTreeSet, HashSet};
 use std::time::{Duration, Instant};
 
+use crate::utils::aocdata::{Part, TestCase};
+use crate::utils::puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle};
 use crate::utils::*;
+use anyhow::{anyhow, Result};
 use debug_plotter;
 use rayon::prelude::*;
 
@@ -138,7 +141,53 @@ pub fn calc_fish_buckets(days: usize) {
     }
 }
 
+pub fn count_fish(input: &str, days: usize) -> Result<i64> {
+    let mut fish = [0i64; 9];
+    for timer in input.trim().split(',') {
+        let timer: usize = timer.parse()?;
+        *fish
+            .get_mut(timer)
+            .ok_or_else(|| anyhow!("Timer {timer} is above 8"))? += 1;
+    }
+    for _day_num in 1..=days {
+        fish.rotate_left(1);
+        fish[6] += fish[8];
+    }
+    Ok(fish.iter().sum())
+}
+
+pub struct AoC2021Day6;
+
+impl SolvePuzzle for AoC2021Day6 {
+    type Output = (i64, i64);
+    fn puzzle_year_day() -> (i32, u32) {
+        (2021, 6)
+    }
+
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
+        let part_a = count_fish(input, ctx.param("days", 80)?)?;
+        let part_b = count_fish(input, ctx.param("days", 256)?)?;
+        Ok((part_a, part_b))
+    }
+
+    fn test_cases() -> Vec<TestCase> {
+        vec![
+            TestCase::new(Part::A, "3,4,3,1,2", 26).with_param("days", 18),
+            TestCase::new(Part::A, "3,4,3,1,2", 5934),
+            TestCase::new(Part::B, "3,4,3,1,2", 26984457539i64),
+        ]
+    }
+}
+
+puzzle_tests!(AoC2021Day6, 2021, 6);
+
 #[test]
 pub fn test_calc_fish() {
     calc_fish_buckets(200);
 }
+
+#[test]
+pub fn bad_timer() {
+    assert_eq!(count_fish("3,4,3,1,2", 18).unwrap(), 26);
+    assert!(count_fish("3,9", 18).is_err());
+}
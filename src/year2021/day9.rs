@@ -1,9 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use hashbrown::HashMap;
 
 use crate::utils::*;
 
 struct HeightMap {
-    pub world: HashMap<IVec2, i32>,
+    pub world: World<i32>,
     pub max_x: i32,
     pub max_y: i32,
 }
@@ -26,9 +26,9 @@ impl HeightMap {
 
     fn is_low_point(&self, pos: IVec2) -> bool {
         let positions = HeightMap::get_neighbors(pos);
-        let height = self.world[&pos];
+        let height = self.world.world[&pos];
         positions.iter().all(|pos| {
-            if let Some(neigh_height) = self.world.get(pos) {
+            if let Some(neigh_height) = self.world.world.get(pos) {
                 *neigh_height > height
             } else {
                 true
@@ -52,25 +52,10 @@ impl HeightMap {
             panic!("trying to calc basin from non low point");
         }
 
-        let mut stack = vec![pos];
-        let mut visited: HashSet<IVec2> = HashSet::new();
-        let mut size = 0;
-        while let Some(curr_pos) = stack.pop() {
-            if visited.contains(&curr_pos) {
-                continue;
-            }
-            visited.insert(curr_pos);
-            if let Some(&height) = self.world.get(&curr_pos) {
-                if height < 9 {
-                    let neighbors = HeightMap::get_neighbors(curr_pos);
-                    for neighbor in neighbors {
-                        stack.push(neighbor);
-                    }
-                    size += 1;
-                }
-            }
-        }
-        size
+        flood_fill(&self.world, pos, Connectivity::Four, |_, height| {
+            *height < 9
+        })
+        .len() as i32
     }
 }
 
@@ -97,14 +82,14 @@ pub fn day_9_part_1() {
             max_y = max_y.max(y);
         }
         let map = HeightMap {
-            world,
+            world: World { world },
             max_x: max_x as i32,
             max_y: max_y as i32,
         };
         let mut low_count = 0;
         let mut low_sum = 0;
         for low in map.get_low_points() {
-            let height = map.world[&low];
+            let height = map.world.world[&low];
             low_count += 1;
             low_sum += 1 + height;
         }
@@ -135,7 +120,7 @@ pub fn day_9_part_2() {
             max_y = max_y.max(y);
         }
         let map = HeightMap {
-            world,
+            world: World { world },
             max_x: max_x as i32,
             max_y: max_y as i32,
         };
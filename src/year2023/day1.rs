@@ -1,6 +1,6 @@
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -46,7 +46,7 @@ impl SolvePuzzle for AoC2023Day1 {
         (2023, 1)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let x = input
             .lines()
             .map(|line| {
@@ -89,10 +89,10 @@ impl SolvePuzzle for AoC2023Day1 {
 
                 let l = line.chars().find(|a| a.is_numeric()).unwrap();
                 let r = line.chars().rev().find(|a| a.is_numeric()).unwrap();
-                dbg!(
-                    (l.to_string().parse::<i32>().unwrap() * 10)
-                        + r.to_string().parse::<i32>().unwrap()
-                )
+                let value = (l.to_string().parse::<i32>().unwrap() * 10)
+                    + r.to_string().parse::<i32>().unwrap();
+                ctx.debug(format_args!("{line} -> {value}"));
+                value
             })
             .sum();
 
@@ -133,10 +133,4 @@ impl SolvePuzzle for AoC2023Day1 {
     }
 }
 //29, 83, 13, 24, 42, 14, and 76
-#[test]
-fn day1() -> Result<()> {
-    AoC2023Day1::run_tests()?;
-    let res = AoC2023Day1::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day1, 2023, 1);
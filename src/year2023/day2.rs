@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -32,7 +32,6 @@ impl FromStr for Game {
             .split("; ")
             .map(|round| round.parse().unwrap())
             .collect_vec();
-        dbg!(id);
         Ok(Self {
             id: id.parse()?,
             rounds: rounds,
@@ -52,7 +51,6 @@ impl FromStr for Round {
 
         for draw in s.split(", ") {
             let (x, color) = draw.split_once(" ").unwrap();
-            dbg!(x, color);
             match color {
                 "red" => ret_val.red = x.parse()?,
                 "green" => ret_val.green = x.parse()?,
@@ -71,11 +69,14 @@ impl SolvePuzzle for AoC2023Day2 {
         (2023, 2)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let games: Vec<Game> = input
             .lines()
             .map(|line| line.parse().unwrap())
             .collect_vec();
+        for game in &games {
+            ctx.debug(format_args!("{game:?}"));
+        }
         let part_a = games
             .iter()
             .filter(|g| {
@@ -123,10 +124,4 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
     }
 }
 
-#[test]
-fn day() -> Result<()> {
-    AoC2023Day2::run_tests()?;
-    let res = AoC2023Day2::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day2, 2023, 2);
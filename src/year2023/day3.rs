@@ -2,9 +2,9 @@ use std::fmt::Display;
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    get_neighbors,
-    puzzle::{PuzzleFns, SolvePuzzle},
-    World,
+    connected_components, get_neighbors,
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
+    Connectivity, ParseOptions, World,
 };
 use anyhow::Result;
 use glam::IVec2;
@@ -13,7 +13,7 @@ use itertools::Itertools;
 
 pub struct AoC2023Day3;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Tile {
     Blank,
     Digit(u32),
@@ -48,77 +48,48 @@ impl TryFrom<char> for Tile {
     }
 }
 
-fn part_numbers(world: &World<Tile>) -> (u32, u32) {
-    let mut ratios = HashMap::new();
-    let mut ratio_counts: HashMap<IVec2, u32> = HashMap::new();
-    for (key, val) in world.world.iter() {
-        if val == &Tile::Gear {
-            ratios.insert(*key, 1);
-        }
-    }
+fn part_numbers(world: &World<Tile>, gears: &[IVec2], ctx: &PuzzleContext) -> (u32, u32) {
+    let numbers = connected_components(
+        world,
+        Connectivity::Rows,
+        |_, tile| matches!(tile, Tile::Digit(_)),
+        |_, _| true,
+    );
+    let mut gear_parts: HashMap<IVec2, Vec<u32>> =
+        gears.iter().map(|gear| (*gear, vec![])).collect();
     let mut sum = 0;
-    for y in world.min_y()..=world.max_y() {
-        let mut num: Option<u32> = None;
-        let mut keep = false;
-        let mut affected_gears = HashSet::new();
-        for x in world.min_x()..=world.max_x() {
-            let pos = IVec2::new(x, y);
-            let cell = &world.world[&pos];
-            match cell {
-                Tile::Blank | Tile::Symbol | Tile::Gear => {
-                    if let Some(curr_num) = num {
-                        if keep {
-                            sum += curr_num;
-                        }
-                        for gear_pos in &affected_gears {
-                            let curr_ratio = ratios[gear_pos];
-                            ratios.insert(*gear_pos, curr_ratio * curr_num);
-                            let curr_count = ratio_counts.get(gear_pos).unwrap_or(&0);
-                            ratio_counts.insert(*gear_pos, *curr_count + 1);
-                        }
-                    }
-                    keep = false;
-                    num = None;
-                    affected_gears.clear();
-                }
-                Tile::Digit(x) => {
-                    if let Some(curr_num) = num {
-                        num = Some((curr_num * 10) + *x)
-                    } else {
-                        num = Some(*x)
-                    }
-                    for neigh in get_neighbors(pos) {
-                        if let Some(n) = world.world.get(&neigh) {
-                            if n == &Tile::Symbol {
-                                keep = true;
-                            }
-                            if n == &Tile::Gear {
-                                keep = true;
-                                affected_gears.insert(neigh);
-                            }
-                        }
-                    }
-                }
-            }
+    for number in &numbers.regions {
+        let value = number
+            .cells
+            .iter()
+            .sorted_by_key(|pos| pos.x)
+            .fold(0, |acc, pos| match world.world[pos] {
+                Tile::Digit(digit) => acc * 10 + digit,
+                _ => acc,
+            });
+        let adjacent: HashSet<IVec2> = number
+            .cells
+            .iter()
+            .flat_map(|pos| get_neighbors(*pos))
+            .filter(|pos| !number.cells.contains(pos))
+            .collect();
+        if adjacent
+            .iter()
+            .any(|pos| matches!(world.world.get(pos), Some(Tile::Symbol | Tile::Gear)))
+        {
+            sum += value;
         }
-        if keep {
-            if let Some(curr_num) = num {
-                sum += curr_num;
-
-                for gear_pos in &affected_gears {
-                    let curr_ratio = ratios[gear_pos];
-                    ratios.insert(*gear_pos, curr_ratio * curr_num);
-                    let curr_count = ratio_counts.get(gear_pos).unwrap_or(&0);
-                    ratio_counts.insert(*gear_pos, *curr_count + 1);
-                }
+        for pos in adjacent {
+            if let Some(parts) = gear_parts.get_mut(&pos) {
+                parts.push(value);
             }
         }
     }
-    dbg!(&ratio_counts);
-    let ratio_sum = ratio_counts
-        .iter()
-        .filter(|(_, &count)| count == 2)
-        .map(|(gear_pos, _count)| dbg!(ratios[gear_pos]))
+    ctx.debug(format_args!("Gear parts {gear_parts:?}"));
+    let ratio_sum = gear_parts
+        .values()
+        .filter(|parts| parts.len() == 2)
+        .map(|parts| parts[0] * parts[1])
         .sum();
     (sum, ratio_sum)
 }
@@ -129,10 +100,16 @@ impl SolvePuzzle for AoC2023Day3 {
         (2023, 3)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
-        let x: World<Tile> = input.try_into()?;
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
+        let (world, markers) = World::parse_markers(
+            input,
+            &ParseOptions::default(),
+            |c| c == '*',
+            None,
+            |token| Tile::try_from(token.chars().next().unwrap()),
+        )?;
 
-        Ok(part_numbers(&x))
+        Ok(part_numbers(&world, markers.all('*'), ctx))
     }
 
     fn test_cases() -> Vec<TestCase> {
@@ -170,10 +147,4 @@ impl SolvePuzzle for AoC2023Day3 {
     }
 }
 
-#[test]
-fn day1() -> Result<()> {
-    AoC2023Day3::run_tests()?;
-    let res = AoC2023Day3::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day3, 2023, 3);
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use hashbrown::HashSet;
@@ -30,7 +30,6 @@ impl FromStr for Game {
         let (id, rounds) = s.split_once(": ").unwrap();
         let id = id.split_whitespace().nth(1).unwrap();
         let (winning, hand) = rounds.split_once(" | ").unwrap();
-        dbg!(winning, hand);
         Ok(Self {
             id: id.parse()?,
             winning: winning
@@ -51,11 +50,14 @@ impl SolvePuzzle for AoC2023Day4 {
         (2023, 4)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let games: Vec<Game> = input
             .lines()
             .map(|line| line.parse().unwrap())
             .collect_vec();
+        for game in &games {
+            ctx.debug(format_args!("{game:?}"));
+        }
         let part_a: u32 = games
             .iter()
             .map(|game| {
@@ -125,10 +127,4 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
     }
 }
 
-#[test]
-fn day() -> Result<()> {
-    AoC2023Day4::run_tests()?;
-    let res = AoC2023Day4::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day4, 2023, 4);
@@ -1,6 +1,6 @@
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -42,7 +42,7 @@ impl SolvePuzzle for AoC2023Day5 {
         (2023, 5)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let mut lines = input.lines();
         let seed_line = lines.next().unwrap();
         let (_, seeds) = seed_line.split_once(": ").unwrap();
@@ -50,7 +50,7 @@ impl SolvePuzzle for AoC2023Day5 {
             .split(" ")
             .map(|s| s.parse::<i64>().unwrap())
             .collect_vec();
-        dbg!(&seeds);
+        ctx.debug(format_args!("Seeds {seeds:?}"));
         lines.next();
         lines.next();
         let mut maps = Vec::new();
@@ -89,26 +89,41 @@ impl SolvePuzzle for AoC2023Day5 {
         let seeds_b = input.lines().next().unwrap();
         let (_, seeds) = seeds_b.split_once(": ").unwrap();
 
+        let seed_count = seeds
+            .split(" ")
+            .tuples()
+            .map(|(_, len)| len.parse::<u64>().unwrap())
+            .sum();
+        let progress = ctx.progress("Seeds", Some(seed_count));
+        // Progress is counted per chunk, counting every seed would contend on the atomic.
+        const CHUNK: i64 = 1 << 16;
         let part_b = seeds
             .split(" ")
             .tuples()
             .flat_map(|(start, len)| {
                 let start: i64 = start.parse().unwrap();
-                let len: i64 = len.parse().unwrap();
-                start..(start + len)
+                let end = start + len.parse::<i64>().unwrap();
+                (start..end)
+                    .step_by(CHUNK as usize)
+                    .map(move |chunk| chunk..(chunk + CHUNK).min(end))
             })
-            .enumerate()
+            .take_while(|_| !ctx.is_cancelled())
             .par_bridge()
-            .map(|(idx, mut seed)| {
-                if idx % 2_i64.pow(24) as usize == 0 {
-                    dbg!(idx);
-                }
-                for map in &maps {
-                    seed = transform(seed, map);
-                }
-                seed
+            .filter_map(|chunk| {
+                let len = (chunk.end - chunk.start) as u64;
+                let lowest = chunk
+                    .map(|mut seed| {
+                        for map in &maps {
+                            seed = transform(seed, map);
+                        }
+                        seed
+                    })
+                    .min();
+                progress.inc(len);
+                lowest
             })
             .min();
+        ctx.check_cancelled()?;
 
         Ok((Some(part_a), part_b))
     }
@@ -193,10 +208,4 @@ humidity-to-location map:
     }
 }
 
-#[test]
-fn day1() -> Result<()> {
-    AoC2023Day5::run_tests()?;
-    let res = AoC2023Day5::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day5, 2023, 5);
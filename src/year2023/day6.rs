@@ -1,6 +1,6 @@
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -14,7 +14,7 @@ impl SolvePuzzle for AoC2023Day6 {
         (2023, 6)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         let (time_line, dist_line) = input.lines().tuples().next().unwrap();
         let times: Vec<u32> = time_line
             .split_whitespace()
@@ -86,10 +86,4 @@ Distance:  9  40  200",
     }
 }
 
-#[test]
-fn run() -> Result<()> {
-    AoC2023Day6::run_tests()?;
-    let res = AoC2023Day6::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day6, 2023, 6);
@@ -2,7 +2,7 @@ use std::{cmp::Ordering, collections::HashMap};
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle, Verbosity},
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -254,7 +254,7 @@ impl SolvePuzzle for AoC2023Day7 {
         (2023, 7)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, ctx: &PuzzleContext) -> Result<Self::Output> {
         let mut hands = input
             .lines()
             .map(|line| {
@@ -285,10 +285,13 @@ impl SolvePuzzle for AoC2023Day7 {
             .enumerate()
             .map(|(rank, (_, bid))| (rank as i32 + 1) * *bid)
             .sum();
-        dbg!(hands_j
-            .iter()
-            .filter(|x| x.0.cards.contains(&CardJ::CJ) && x.0.hand_type == HandType::FullHouse)
-            .collect_vec());
+        if ctx.enabled(Verbosity::Debug) {
+            let full_houses = hands_j
+                .iter()
+                .filter(|x| x.0.cards.contains(&CardJ::CJ) && x.0.hand_type == HandType::FullHouse)
+                .collect_vec();
+            ctx.debug(format_args!("Full houses with jokers {full_houses:?}"));
+        }
         Ok((Some(part_a), Some(part_b)))
     }
 
@@ -317,10 +320,4 @@ QQQJA 483",
     }
 }
 
-#[test]
-fn run() -> Result<()> {
-    AoC2023Day7::run_tests()?;
-    let res = AoC2023Day7::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day7, 2023, 7);
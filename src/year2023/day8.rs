@@ -2,7 +2,7 @@ use std::iter::Repeat;
 
 use crate::utils::{
     aocdata::{Part, TestCase},
-    puzzle::{PuzzleFns, SolvePuzzle},
+    puzzle::{puzzle_tests, PuzzleContext, PuzzleFns, SolvePuzzle},
 };
 use anyhow::Result;
 use hashbrown::HashMap;
@@ -44,12 +44,12 @@ fn find_cycle(map: &HashMap<&str, (&str, &str)>, moves: &[char], start: &str) ->
 }
 
 impl SolvePuzzle for AoC2023Day8 {
-    type Output = (Option<i32>, Option<usize>);
+    type Output = (usize, usize);
     fn puzzle_year_day() -> (i32, u32) {
         (2023, 8)
     }
 
-    fn solve(input: &str) -> Result<Self::Output> {
+    fn solve(input: &str, _ctx: &PuzzleContext) -> Result<Self::Output> {
         let mut lines = input.lines();
         let moves = lines.next().unwrap().chars().collect_vec();
         lines.next().unwrap();
@@ -91,7 +91,7 @@ impl SolvePuzzle for AoC2023Day8 {
             .reduce(|acc, x| num::integer::lcm(acc, x))
             .unwrap();
 
-        Ok((Some(curr_move as i32), Some(b)))
+        Ok((curr_move, b))
     }
 
     fn test_cases() -> Vec<TestCase> {
@@ -123,10 +123,4 @@ XXX = (XXX, XXX)",
     }
 }
 
-#[test]
-fn run() -> Result<()> {
-    AoC2023Day8::run_tests()?;
-    let res = AoC2023Day8::try_submit()?;
-    eprintln!("{res:?}");
-    Ok(())
-}
+puzzle_tests!(AoC2023Day8, 2023, 8);